@@ -1,41 +1,29 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
-mod errors;
-
 pub use self::az_groups::AZGroupsRef;
 
 #[ink::contract]
 mod az_groups {
-    use crate::errors::AZGroupsError;
+    use az_groups_types::{AZGroupsError, AZGroupsMembership, Permission, Role};
     use ink::{
-        prelude::string::{String, ToString},
+        prelude::{
+            format,
+            string::{String, ToString},
+            vec::Vec,
+        },
         storage::Mapping,
     };
 
-    // === ENUMS ===
-    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
-    #[cfg_attr(
-        feature = "std",
-        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
-    )]
-    pub enum Role {
-        Banned,
-        Applicant,
-        Member,
-        Admin,
-        SuperAdmin,
-    }
-    impl Role {
-        fn to_int(&self) -> u8 {
-            match *self {
-                Role::Banned => 0,
-                Role::Applicant => 1,
-                Role::Member => 2,
-                Role::Admin => 3,
-                Role::SuperAdmin => 4,
-            }
-        }
-    }
+    const APPLICATION_NOTE_LENGTH_LIMIT: usize = 280;
+    const AZERO_ID_LENGTH_LIMIT: usize = 64;
+    const DESCRIPTION_LENGTH_LIMIT: usize = 500;
+    const URL_LENGTH_LIMIT: usize = 256;
+
+    // === TYPES ===
+    // (group_id, role)
+    type GroupRoleKey = (u32, Role);
+    // (group_id, user)
+    type GroupUserKey = (u32, AccountId);
 
     // === EVENTS ===
     #[ink(event)]
@@ -43,6 +31,56 @@ mod az_groups {
         #[ink(topic)]
         id: u32,
         name: String,
+        description: Option<String>,
+        website: Option<String>,
+        logo_url: Option<String>,
+        azero_id: Option<String>,
+    }
+
+    #[ink(event)]
+    pub struct RolePermissionsUpdate {
+        #[ink(topic)]
+        group_id: u32,
+        role: Role,
+        permissions: Vec<Permission>,
+    }
+
+    #[ink(event)]
+    pub struct SuperAdminTransferAccept {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SuperAdminTransferCreate {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        from: AccountId,
+        #[ink(topic)]
+        to: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct SuperAdminTransferDestroy {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        to: AccountId,
+        caller: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct UserPermissionsUpdate {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        user: AccountId,
+        permissions: Vec<Permission>,
     }
 
     #[ink(event)]
@@ -51,6 +89,27 @@ mod az_groups {
         id: u32,
         name: String,
         enabled: bool,
+        membership_mode: MembershipMode,
+        description: Option<String>,
+        website: Option<String>,
+        logo_url: Option<String>,
+        azero_id: Option<String>,
+    }
+
+    #[ink(event)]
+    pub struct ApplicationCooldownUpdate {
+        #[ink(topic)]
+        group_id: u32,
+        cooldown: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct GroupUserApprove {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        user: AccountId,
+        caller: AccountId,
     }
 
     #[ink(event)]
@@ -70,6 +129,15 @@ mod az_groups {
         user: AccountId,
     }
 
+    #[ink(event)]
+    pub struct GroupUserReject {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        user: AccountId,
+        caller: AccountId,
+    }
+
     #[ink(event)]
     pub struct GroupUserUpdate {
         #[ink(topic)]
@@ -79,7 +147,42 @@ mod az_groups {
         role: Role,
     }
 
+    #[ink(event)]
+    pub struct InvitationAccept {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        user: AccountId,
+        role: Role,
+    }
+
+    #[ink(event)]
+    pub struct InvitationCreate {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        user: AccountId,
+        role: Role,
+        inviter: AccountId,
+        expires_at: Option<Timestamp>,
+    }
+
+    #[ink(event)]
+    pub struct InvitationDestroy {
+        #[ink(topic)]
+        group_id: u32,
+        #[ink(topic)]
+        user: AccountId,
+        caller: AccountId,
+    }
+
     // === STRUCTS ===
+    #[derive(Debug, Clone, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct Config {
+        groups_total: u32,
+    }
+
     #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
     #[cfg_attr(
         feature = "std",
@@ -89,6 +192,11 @@ mod az_groups {
         id: u32,
         name: String,
         enabled: bool,
+        membership_mode: MembershipMode,
+        description: Option<String>,
+        website: Option<String>,
+        logo_url: Option<String>,
+        azero_id: Option<String>,
     }
 
     // 0: Banned
@@ -105,12 +213,72 @@ mod az_groups {
         role: Role,
     }
 
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Invitation {
+        group_id: u32,
+        user: AccountId,
+        role: Role,
+        inviter: AccountId,
+        expires_at: Option<Timestamp>,
+    }
+
+    // How accounts can join a group.
+    // Open: anyone who applies joins as a member.
+    // Approval: applicants join once approved by an admin.
+    // InviteOnly: accounts can only join through an invitation.
+    // Closed: no new accounts can join.
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum MembershipMode {
+        Open,
+        Approval,
+        InviteOnly,
+        Closed,
+    }
+
+    // A pending handover of a super admin's role, keyed by group and recipient.
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct SuperAdminTransfer {
+        group_id: u32,
+        from: AccountId,
+        to: AccountId,
+    }
+
     #[ink(storage)]
     pub struct AZGroups {
+        application_cooldowns: Mapping<u32, Timestamp>,
+        application_notes: Mapping<(u32, AccountId), String>,
+        // (user, position) => group_id
+        group_ids_by_user: Mapping<(AccountId, u32), u32>,
+        // (user, group_id) => position
+        group_positions_by_user: Mapping<(AccountId, u32), u32>,
         groups: Mapping<u32, Group>,
+        groups_count_by_user: Mapping<AccountId, u32>,
         group_id_by_name: Mapping<String, u32>,
         groups_total: u32,
         group_users: Mapping<(u32, AccountId), GroupUser>,
+        group_users_count_by_role: Mapping<(u32, Role), u32>,
+        invitations: Mapping<(u32, AccountId), Invitation>,
+        permissions_by_role: Mapping<GroupRoleKey, Vec<Permission>>,
+        permissions_by_user: Mapping<GroupUserKey, Vec<Permission>>,
+        rejected_at: Mapping<(u32, AccountId), Timestamp>,
+        super_admin_transfers: Mapping<(u32, AccountId), SuperAdminTransfer>,
+        // (group_id, user) => position
+        user_positions_by_group: Mapping<GroupUserKey, u32>,
+        // (group_id, position) => user
+        users_by_group: Mapping<(u32, u32), AccountId>,
+        users_count_by_group: Mapping<u32, u32>,
     }
     impl Default for AZGroups {
         fn default() -> Self {
@@ -121,17 +289,110 @@ mod az_groups {
         #[ink(constructor)]
         pub fn new() -> Self {
             Self {
+                application_cooldowns: Mapping::default(),
+                application_notes: Mapping::default(),
+                group_ids_by_user: Mapping::default(),
+                group_positions_by_user: Mapping::default(),
                 groups: Mapping::default(),
+                groups_count_by_user: Mapping::default(),
                 group_id_by_name: Mapping::default(),
                 groups_total: 0,
                 group_users: Mapping::default(),
+                group_users_count_by_role: Mapping::default(),
+                invitations: Mapping::default(),
+                permissions_by_role: Mapping::default(),
+                permissions_by_user: Mapping::default(),
+                rejected_at: Mapping::default(),
+                super_admin_transfers: Mapping::default(),
+                user_positions_by_group: Mapping::default(),
+                users_by_group: Mapping::default(),
+                users_count_by_group: Mapping::default(),
+            }
+        }
+
+        // The application cooldown is how long after being rejected, in milliseconds,
+        // before an account can apply to the group again.
+        #[ink(message)]
+        pub fn application_cooldown(&self, group_id: u32) -> Timestamp {
+            self.application_cooldowns.get(group_id).unwrap_or(0)
+        }
+
+        #[ink(message)]
+        pub fn application_note(&self, group_id: u32, user: AccountId) -> Option<String> {
+            self.application_notes.get((group_id, user))
+        }
+
+        #[ink(message)]
+        pub fn config(&self) -> Config {
+            Config {
+                groups_total: self.groups_total,
+            }
+        }
+
+        #[ink(message)]
+        pub fn group_users_approve(
+            &mut self,
+            group_id: u32,
+            user: AccountId,
+        ) -> Result<GroupUser, AZGroupsError> {
+            let caller: AccountId = Self::env().caller();
+            self.authorise_applicant_review(group_id, caller, user)?;
+
+            let group_user: GroupUser = GroupUser { role: Role::Member };
+            self.set_group_user(group_id, user, &group_user);
+
+            // emit events
+            self.env().emit_event(GroupUserApprove {
+                group_id,
+                user,
+                caller,
+            });
+            self.env().emit_event(GroupUserUpdate {
+                group_id,
+                user,
+                role: group_user.role.clone(),
+            });
+
+            Ok(group_user)
+        }
+
+        // Returns the number of users in the group, or only those with role_filter.
+        #[ink(message)]
+        pub fn group_users_count(&self, group_id: u32, role_filter: Option<Role>) -> u32 {
+            match role_filter {
+                Some(role) => self
+                    .group_users_count_by_role
+                    .get((group_id, role))
+                    .unwrap_or(0),
+                None => self.users_count_by_group.get(group_id).unwrap_or(0),
             }
         }
 
+        // In an open group the caller joins as a member, otherwise they join as an applicant.
+        // An applicant can include a note of up to 280 bytes about who they are.
+        // An account that has been rejected must wait for the group's application cooldown to pass.
         #[ink(message)]
-        pub fn group_users_create(&mut self, group_id: u32) -> Result<GroupUser, AZGroupsError> {
+        pub fn group_users_create(
+            &mut self,
+            group_id: u32,
+            note: Option<String>,
+        ) -> Result<GroupUser, AZGroupsError> {
             // check if group exists
-            self.groups_show(group_id)?;
+            let group: Group = self.groups_show(group_id)?;
+            let role: Role = match group.membership_mode {
+                MembershipMode::Open => Role::Member,
+                MembershipMode::Approval => Role::Applicant,
+                MembershipMode::InviteOnly => {
+                    return Err(AZGroupsError::UnprocessableEntity(
+                        "Group is invite only".to_string(),
+                    ))
+                }
+                MembershipMode::Closed => {
+                    return Err(AZGroupsError::UnprocessableEntity(
+                        "Group is closed".to_string(),
+                    ))
+                }
+            };
             // check if group user already exists
             let user: AccountId = Self::env().caller();
             if self.group_users.get((group_id, user)).is_some() {
@@ -139,12 +400,36 @@ mod az_groups {
                     "Group user has already been taken".to_string(),
                 ));
             }
+            let note_formatted: Option<String> = note
+                .map(|note| note.trim().to_string())
+                .filter(|note| !note.is_empty());
+            if let Some(ref note_unwrapped) = note_formatted {
+                if note_unwrapped.len() > APPLICATION_NOTE_LENGTH_LIMIT {
+                    return Err(AZGroupsError::UnprocessableEntity(format!(
+                        "Note can't be longer than {APPLICATION_NOTE_LENGTH_LIMIT} bytes"
+                    )));
+                }
+            }
+            if let Some(rejected_at) = self.rejected_at.get((group_id, user)) {
+                if Self::env().block_timestamp()
+                    < rejected_at.saturating_add(self.application_cooldown(group_id))
+                {
+                    return Err(AZGroupsError::UnprocessableEntity(
+                        "Application cooldown has not passed".to_string(),
+                    ));
+                }
+                self.rejected_at.remove((group_id, user));
+            }
 
             // Create and set group user
-            let group_user: GroupUser = GroupUser {
-                role: Role::Applicant,
-            };
-            self.group_users.insert((group_id, user), &group_user);
+            let group_user: GroupUser = GroupUser { role };
+            self.set_group_user(group_id, user, &group_user);
+            if let Some(note_unwrapped) = note_formatted {
+                if group_user.role == Role::Applicant {
+                    self.application_notes
+                        .insert((group_id, user), &note_unwrapped);
+                }
+            }
 
             // emit event
             self.env().emit_event(GroupUserCreate {
@@ -156,10 +441,9 @@ mod az_groups {
             Ok(group_user)
         }
 
-        // User can leave the group, as long as they aren't a super admin or banned.
-        // Super admins can't kick themselves because there's a chance that the group would be left without one.
-        // The only way a super admin can leave the group is to be kicked by another super admin.
-        // User can be kicked by an admin or super-admin, as long as they are of the same role level or less.
+        // User can leave the group, as long as they aren't banned.
+        // A super admin can only leave if the group has another super admin.
+        // User can be kicked by a user who can manage members, as long as they are of the same role level or less.
         #[ink(message)]
         pub fn group_users_destroy(
             &mut self,
@@ -171,15 +455,18 @@ mod az_groups {
             let user_group_user: GroupUser = self.group_users_show(group_id, user)?;
             let caller_group_user_role_as_int: u8 = caller_group_user.role.to_int();
             if caller == user {
-                if caller_group_user_role_as_int == 4 || caller_group_user_role_as_int == 0 {
+                if caller_group_user_role_as_int == 0 {
                     return Err(AZGroupsError::Unauthorised);
                 }
-            } else if caller_group_user_role_as_int < 3
+            } else if !self.has_permission(group_id, caller, Permission::ManageMembers)
                 || caller_group_user_role_as_int < user_group_user.role.to_int()
             {
                 return Err(AZGroupsError::Unauthorised);
             }
-            self.group_users.remove((group_id, user));
+            if user_group_user.role == Role::SuperAdmin {
+                self.validate_super_admin_remains(group_id)?;
+            }
+            self.remove_group_user(group_id, user);
 
             // emit event
             self.env().emit_event(GroupUserDestroy { group_id, user });
@@ -187,6 +474,68 @@ mod az_groups {
             Ok(())
         }
 
+        // Users are returned in the order that they joined the group,
+        // except that when a user leaves, the last user to join takes their place.
+        // When role_filter is present, users with other roles are skipped before paging.
+        #[ink(message)]
+        pub fn group_users_index(
+            &self,
+            group_id: u32,
+            role_filter: Option<Role>,
+            page: u32,
+            size: u8,
+        ) -> Vec<(AccountId, Role)> {
+            let positions = 0..self.users_count_by_group.get(group_id).unwrap_or(0);
+            let user_at = |position: u32| {
+                self.users_by_group
+                    .get((group_id, position))
+                    .and_then(|user| {
+                        self.group_users
+                            .get((group_id, user))
+                            .map(|group_user| (user, group_user.role))
+                    })
+            };
+            let skipped: usize = (page as usize).saturating_mul(size.into());
+            match role_filter {
+                Some(role_filter) => positions
+                    .filter_map(user_at)
+                    .filter(|(_user, role)| *role == role_filter)
+                    .skip(skipped)
+                    .take(size.into())
+                    .collect(),
+                None => positions
+                    .skip(skipped)
+                    .take(size.into())
+                    .filter_map(user_at)
+                    .collect(),
+            }
+        }
+
+        // Removes the applicant and starts their application cooldown.
+        #[ink(message)]
+        pub fn group_users_reject(
+            &mut self,
+            group_id: u32,
+            user: AccountId,
+        ) -> Result<(), AZGroupsError> {
+            let caller: AccountId = Self::env().caller();
+            self.authorise_applicant_review(group_id, caller, user)?;
+
+            self.remove_group_user(group_id, user);
+            self.rejected_at
+                .insert((group_id, user), &Self::env().block_timestamp());
+
+            // emit events
+            self.env().emit_event(GroupUserReject {
+                group_id,
+                user,
+                caller,
+            });
+            self.env().emit_event(GroupUserDestroy { group_id, user });
+
+            Ok(())
+        }
+
         #[ink(message)]
         pub fn group_users_show(
             &self,
@@ -212,8 +561,8 @@ mod az_groups {
             }
             let caller_group_user: GroupUser = self.group_users_show(group_id, caller)?;
             let caller_group_user_as_int: u8 = caller_group_user.role.to_int();
-            // Only an admin can make changes
-            if caller_group_user_as_int < 3 {
+            // Only a user who can manage members can make changes
+            if !self.has_permission(group_id, caller, Permission::ManageMembers) {
                 return Err(AZGroupsError::Unauthorised);
             }
             let mut user_group_user: GroupUser = self.group_users_show(group_id, user)?;
@@ -223,9 +572,12 @@ mod az_groups {
             if role_as_int > caller_group_user_as_int {
                 return Err(AZGroupsError::Unauthorised);
             }
+            if user_group_user.role == Role::SuperAdmin && role != Role::SuperAdmin {
+                self.validate_super_admin_remains(group_id)?;
+            }
 
             user_group_user.role = role.clone();
-            self.group_users.insert((group_id, user), &user_group_user);
+            self.set_group_user(group_id, user, &user_group_user);
 
             // emit event
             self.env().emit_event(GroupUserUpdate {
@@ -237,10 +589,45 @@ mod az_groups {
             Ok(user_group_user)
         }
 
+        // Groups are returned in the order that the user joined them, along with the user's role.
+        // When the user leaves a group, the group they joined last takes its place.
+        #[ink(message)]
+        pub fn groups_by_user(&self, user: AccountId, page: u32, size: u8) -> Vec<(Group, Role)> {
+            (0..self.groups_count_by_user.get(user).unwrap_or(0))
+                .skip((page as usize).saturating_mul(size.into()))
+                .take(size.into())
+                .filter_map(|position| {
+                    let group_id: u32 = self.group_ids_by_user.get((user, position))?;
+                    let group: Group = self.groups.get(group_id)?;
+                    let group_user: GroupUser = self.group_users.get((group_id, user))?;
+                    Some((group, group_user.role))
+                })
+                .collect()
+        }
+
+        // The AZERO.ID is only checked for length as AZ Groups does not resolve it.
         #[ink(message)]
-        pub fn groups_create(&mut self, name: String) -> Result<Group, AZGroupsError> {
+        pub fn groups_create(
+            &mut self,
+            name: String,
+            description: Option<String>,
+            website: Option<String>,
+            logo_url: Option<String>,
+            azero_id: Option<String>,
+        ) -> Result<Group, AZGroupsError> {
             let formatted_name: String = name.trim().to_string();
             AZGroups::validate_group_name_presence(formatted_name.clone())?;
+            let description: Option<String> = AZGroups::format_profile_field(
+                description,
+                "Description",
+                DESCRIPTION_LENGTH_LIMIT,
+            )?;
+            let website: Option<String> =
+                AZGroups::format_profile_field(website, "Website", URL_LENGTH_LIMIT)?;
+            let logo_url: Option<String> =
+                AZGroups::format_profile_field(logo_url, "Logo url", URL_LENGTH_LIMIT)?;
+            let azero_id: Option<String> =
+                AZGroups::format_profile_field(azero_id, "AZERO.ID", AZERO_ID_LENGTH_LIMIT)?;
             if self.groups_total == u32::MAX {
                 return Err(AZGroupsError::UnprocessableEntity(
                     "Group limit reached".to_string(),
@@ -257,6 +644,11 @@ mod az_groups {
                 id: self.groups_total,
                 name: formatted_name.clone(),
                 enabled: true,
+                membership_mode: MembershipMode::Approval,
+                description,
+                website,
+                logo_url,
+                azero_id,
             };
             self.groups.insert(group.id, &group);
 
@@ -267,7 +659,7 @@ mod az_groups {
             let group_user: GroupUser = GroupUser {
                 role: Role::SuperAdmin,
             };
-            self.group_users.insert((group.id, user), &group_user);
+            self.set_group_user(group.id, user, &group_user);
 
             // Increase groups_total
             self.groups_total = self.groups_total.checked_add(1).unwrap();
@@ -276,6 +668,10 @@ mod az_groups {
             self.env().emit_event(Create {
                 id: group.id,
                 name: formatted_name,
+                description: group.description.clone(),
+                website: group.website.clone(),
+                logo_url: group.logo_url.clone(),
+                azero_id: group.azero_id.clone(),
             });
             self.env().emit_event(GroupUserCreate {
                 group_id: group.id,
@@ -295,6 +691,18 @@ mod az_groups {
             }
         }
 
+        // Groups are returned in order of id.
+        // When enabled_only is true, disabled groups are skipped before paging.
+        #[ink(message)]
+        pub fn groups_index(&self, enabled_only: bool, page: u32, size: u8) -> Vec<Group> {
+            (0..self.groups_total)
+                .filter_map(|id| self.groups.get(id))
+                .filter(|group| !enabled_only || group.enabled)
+                .skip((page as usize).saturating_mul(size.into()))
+                .take(size.into())
+                .collect()
+        }
+
         #[ink(message)]
         pub fn groups_show(&self, id: u32) -> Result<Group, AZGroupsError> {
             self.groups
@@ -302,17 +710,23 @@ mod az_groups {
                 .ok_or(AZGroupsError::NotFound("Group".to_string()))
         }
 
+        #[allow(clippy::too_many_arguments)]
         #[ink(message)]
         pub fn groups_update(
             &mut self,
             id: u32,
             name: String,
             enabled: bool,
+            membership_mode: MembershipMode,
+            description: Option<String>,
+            website: Option<String>,
+            logo_url: Option<String>,
+            azero_id: Option<String>,
         ) -> Result<Group, AZGroupsError> {
             let mut group: Group = self.groups_show(id)?;
             let caller: AccountId = Self::env().caller();
-            let caller_group_user: GroupUser = self.group_users_show(id, caller)?;
-            if caller_group_user.role != Role::SuperAdmin {
+            self.group_users_show(id, caller)?;
+            if !self.has_permission(id, caller, Permission::EditGroup) {
                 return Err(AZGroupsError::Unauthorised);
             }
 
@@ -332,6 +746,17 @@ mod az_groups {
                 self.group_id_by_name.insert(new_key, &id);
             }
             group.enabled = enabled;
+            group.membership_mode = membership_mode;
+            group.description = AZGroups::format_profile_field(
+                description,
+                "Description",
+                DESCRIPTION_LENGTH_LIMIT,
+            )?;
+            group.website = AZGroups::format_profile_field(website, "Website", URL_LENGTH_LIMIT)?;
+            group.logo_url =
+                AZGroups::format_profile_field(logo_url, "Logo url", URL_LENGTH_LIMIT)?;
+            group.azero_id =
+                AZGroups::format_profile_field(azero_id, "AZERO.ID", AZERO_ID_LENGTH_LIMIT)?;
             self.groups.insert(id, &group);
 
             // emit event
@@ -339,392 +764,2212 @@ mod az_groups {
                 id,
                 name: group.name.clone(),
                 enabled: group.enabled,
+                membership_mode: group.membership_mode.clone(),
+                description: group.description.clone(),
+                website: group.website.clone(),
+                logo_url: group.logo_url.clone(),
+                azero_id: group.azero_id.clone(),
             });
 
             Ok(group)
         }
 
-        // Convenience method so that other contract can get this info without having to call two functions
-        // 1. Check that the group is enabled
-        // 2. Check that user has a role with the group greater than or equal to two
         #[ink(message)]
-        pub fn validate_membership(
-            &self,
-            group_id: u32,
-            user: AccountId,
-        ) -> Result<Role, AZGroupsError> {
-            let group: Group = self.groups_show(group_id)?;
-            if !group.enabled {
-                return Err(AZGroupsError::GroupDisabled);
-            }
-            let group_user: GroupUser = self.group_users_show(group_id, user)?;
-            if group_user.role.to_int() < 2 {
-                return Err(AZGroupsError::NotAMember);
+        pub fn groups_update_application_cooldown(
+            &mut self,
+            id: u32,
+            cooldown: Timestamp,
+        ) -> Result<Timestamp, AZGroupsError> {
+            self.groups_show(id)?;
+            let caller: AccountId = Self::env().caller();
+            self.group_users_show(id, caller)?;
+            if !self.has_permission(id, caller, Permission::EditGroup) {
+                return Err(AZGroupsError::Unauthorised);
             }
 
-            Ok(group_user.role)
-        }
+            self.application_cooldowns.insert(id, &cooldown);
 
-        fn format_group_name(name: String) -> String {
-            name.trim().to_string()
+            // emit event
+            self.env().emit_event(ApplicationCooldownUpdate {
+                group_id: id,
+                cooldown,
+            });
+
+            Ok(cooldown)
         }
 
-        fn validate_group_name_presence(name: String) -> Result<(), AZGroupsError> {
-            if name.is_empty() {
+        // The invitee becomes a group user with the invited role in one call.
+        // An applicant who is invited is promoted to the invited role.
+        // The inviter must still be able to invite to the role, as their role or permissions may have changed.
+        #[ink(message)]
+        pub fn invitations_accept(&mut self, group_id: u32) -> Result<GroupUser, AZGroupsError> {
+            let caller: AccountId = Self::env().caller();
+            let invitation: Invitation = self.invitations_show(group_id, caller)?;
+            AZGroups::validate_not_closed(&self.groups_show(group_id)?)?;
+            if let Some(expires_at) = invitation.expires_at {
+                if Self::env().block_timestamp() >= expires_at {
+                    return Err(AZGroupsError::UnprocessableEntity(
+                        "Invitation has expired".to_string(),
+                    ));
+                }
+            }
+            if self
+                .authorise_invitation(group_id, invitation.inviter, &invitation.role)
+                .is_err()
+            {
                 return Err(AZGroupsError::UnprocessableEntity(
-                    "Name can't be blank".to_string(),
+                    "Inviter can no longer invite to this role".to_string(),
                 ));
+            }
+            let existing_group_user: Option<GroupUser> = self.group_users.get((group_id, caller));
+            AZGroups::validate_invitable(&existing_group_user)?;
+
+            let group_user: GroupUser = GroupUser {
+                role: invitation.role.clone(),
             };
+            self.set_group_user(group_id, caller, &group_user);
+            self.invitations.remove((group_id, caller));
 
-            Ok(())
+            // emit events
+            self.env().emit_event(InvitationAccept {
+                group_id,
+                user: caller,
+                role: group_user.role.clone(),
+            });
+            if existing_group_user.is_some() {
+                self.env().emit_event(GroupUserUpdate {
+                    group_id,
+                    user: caller,
+                    role: group_user.role.clone(),
+                });
+            } else {
+                self.env().emit_event(GroupUserCreate {
+                    group_id,
+                    user: caller,
+                    role: group_user.role.clone(),
+                });
+            }
+
+            Ok(group_user)
         }
 
-        fn validate_group_name_uniqueness(&self, key: String) -> Result<(), AZGroupsError> {
-            if self.group_id_by_name.get(key).is_some() {
+        // Only a user who can manage invitations can invite, and only to a role between member and their own role.
+        // Invitations can't be created or accepted while the group is closed.
+        // Inviting a user who already has an invitation replaces it.
+        #[ink(message)]
+        pub fn invitations_create(
+            &mut self,
+            group_id: u32,
+            user: AccountId,
+            role: Role,
+            expires_at: Option<Timestamp>,
+        ) -> Result<Invitation, AZGroupsError> {
+            let group: Group = self.groups_show(group_id)?;
+            let caller: AccountId = Self::env().caller();
+            self.authorise_invitation(group_id, caller, &role)?;
+            if role.to_int() < 2 {
                 return Err(AZGroupsError::UnprocessableEntity(
-                    "Group has already been taken".to_string(),
+                    "Role must be member or above".to_string(),
                 ));
             }
+            if let Some(expires_at) = expires_at {
+                if expires_at <= Self::env().block_timestamp() {
+                    return Err(AZGroupsError::UnprocessableEntity(
+                        "Expiry must be in the future".to_string(),
+                    ));
+                }
+            }
+            AZGroups::validate_not_closed(&group)?;
+            AZGroups::validate_invitable(&self.group_users.get((group_id, user)))?;
+
+            let invitation: Invitation = Invitation {
+                group_id,
+                user,
+                role,
+                inviter: caller,
+                expires_at,
+            };
+            self.invitations.insert((group_id, user), &invitation);
+
+            // emit event
+            self.env().emit_event(InvitationCreate {
+                group_id,
+                user,
+                role: invitation.role.clone(),
+                inviter: caller,
+                expires_at,
+            });
+
+            Ok(invitation)
+        }
+
+        // An invitation can be revoked by a user who can manage invitations and whose role is at least the invited role,
+        // or declined by the invitee.
+        #[ink(message)]
+        pub fn invitations_destroy(
+            &mut self,
+            group_id: u32,
+            user: AccountId,
+        ) -> Result<(), AZGroupsError> {
+            let invitation: Invitation = self.invitations_show(group_id, user)?;
+            let caller: AccountId = Self::env().caller();
+            if caller != user {
+                let caller_group_user: GroupUser = self.group_users_show(group_id, caller)?;
+                let caller_group_user_as_int: u8 = caller_group_user.role.to_int();
+                if !self.has_permission(group_id, caller, Permission::ManageInvitations)
+                    || caller_group_user_as_int < invitation.role.to_int()
+                {
+                    return Err(AZGroupsError::Unauthorised);
+                }
+            }
+            self.invitations.remove((group_id, user));
+
+            // emit event
+            self.env().emit_event(InvitationDestroy {
+                group_id,
+                user,
+                caller,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn invitations_show(
+            &self,
+            group_id: u32,
+            user: AccountId,
+        ) -> Result<Invitation, AZGroupsError> {
+            self.invitations
+                .get((group_id, user))
+                .ok_or(AZGroupsError::NotFound("Invitation".to_string()))
+        }
+
+        // Returns the permissions of the role in the group, falling back to the defaults for the role.
+        // Super admins have every permission.
+        #[ink(message)]
+        pub fn role_permissions(&self, group_id: u32, role: Role) -> Vec<Permission> {
+            match role {
+                Role::SuperAdmin => [
+                    Permission::ManageMembers,
+                    Permission::ManageInvitations,
+                    Permission::EditGroup,
+                    Permission::ManageHubRecords,
+                ]
+                .to_vec(),
+                _ => self
+                    .permissions_by_role
+                    .get((group_id, role.clone()))
+                    .unwrap_or_else(|| AZGroups::default_role_permissions(&role)),
+            }
+        }
+
+        // Only a super admin can assign permissions, and only to members and admins.
+        #[ink(message)]
+        pub fn role_permissions_update(
+            &mut self,
+            group_id: u32,
+            role: Role,
+            permissions: Vec<Permission>,
+        ) -> Result<Vec<Permission>, AZGroupsError> {
+            self.authorise_permissions_update(group_id, &role)?;
+            let permissions: Vec<Permission> = AZGroups::dedup_permissions(permissions);
+            self.permissions_by_role
+                .insert((group_id, role.clone()), &permissions);
+
+            // emit event
+            self.env().emit_event(RolePermissionsUpdate {
+                group_id,
+                role,
+                permissions: permissions.clone(),
+            });
+
+            Ok(permissions)
+        }
+
+        // The recipient becomes a super admin and the super admin who started the transfer becomes an admin.
+        // The transfer lapses if the sender is no longer a super admin.
+        #[ink(message)]
+        pub fn super_admin_transfers_accept(
+            &mut self,
+            group_id: u32,
+        ) -> Result<GroupUser, AZGroupsError> {
+            let caller: AccountId = Self::env().caller();
+            let transfer: SuperAdminTransfer = self.super_admin_transfers_show(group_id, caller)?;
+            let mut from_group_user: GroupUser = self.group_users_show(group_id, transfer.from)?;
+            if from_group_user.role != Role::SuperAdmin {
+                return Err(AZGroupsError::Unauthorised);
+            }
+            let existing_group_user: Option<GroupUser> = self.group_users.get((group_id, caller));
+            if let Some(ref group_user) = existing_group_user {
+                if group_user.role == Role::Banned {
+                    return Err(AZGroupsError::Unauthorised);
+                }
+            }
+
+            let group_user: GroupUser = GroupUser {
+                role: Role::SuperAdmin,
+            };
+            self.set_group_user(group_id, caller, &group_user);
+            from_group_user.role = Role::Admin;
+            self.set_group_user(group_id, transfer.from, &from_group_user);
+            self.super_admin_transfers.remove((group_id, caller));
+
+            // emit events
+            self.env().emit_event(SuperAdminTransferAccept {
+                group_id,
+                from: transfer.from,
+                to: caller,
+            });
+            if existing_group_user.is_some() {
+                self.env().emit_event(GroupUserUpdate {
+                    group_id,
+                    user: caller,
+                    role: group_user.role.clone(),
+                });
+            } else {
+                self.env().emit_event(GroupUserCreate {
+                    group_id,
+                    user: caller,
+                    role: group_user.role.clone(),
+                });
+            }
+            self.env().emit_event(GroupUserUpdate {
+                group_id,
+                user: transfer.from,
+                role: from_group_user.role,
+            });
+
+            Ok(group_user)
+        }
+
+        // A super admin nominates an account to take over their role.
+        // Nominating an account that already has a pending transfer replaces it.
+        #[ink(message)]
+        pub fn super_admin_transfers_create(
+            &mut self,
+            group_id: u32,
+            to: AccountId,
+        ) -> Result<SuperAdminTransfer, AZGroupsError> {
+            let caller: AccountId = Self::env().caller();
+            let caller_group_user: GroupUser = self.group_users_show(group_id, caller)?;
+            if caller_group_user.role != Role::SuperAdmin {
+                return Err(AZGroupsError::Unauthorised);
+            }
+            if caller == to {
+                return Err(AZGroupsError::UnprocessableEntity(
+                    "Recipient must be a different account".to_string(),
+                ));
+            }
+
+            let transfer: SuperAdminTransfer = SuperAdminTransfer {
+                group_id,
+                from: caller,
+                to,
+            };
+            self.super_admin_transfers.insert((group_id, to), &transfer);
+
+            // emit event
+            self.env().emit_event(SuperAdminTransferCreate {
+                group_id,
+                from: caller,
+                to,
+            });
+
+            Ok(transfer)
+        }
+
+        // A transfer can be cancelled by the sender or declined by the recipient.
+        #[ink(message)]
+        pub fn super_admin_transfers_destroy(
+            &mut self,
+            group_id: u32,
+            to: AccountId,
+        ) -> Result<(), AZGroupsError> {
+            let transfer: SuperAdminTransfer = self.super_admin_transfers_show(group_id, to)?;
+            let caller: AccountId = Self::env().caller();
+            if caller != transfer.from && caller != transfer.to {
+                return Err(AZGroupsError::Unauthorised);
+            }
+            self.super_admin_transfers.remove((group_id, to));
+
+            // emit event
+            self.env().emit_event(SuperAdminTransferDestroy {
+                group_id,
+                to,
+                caller,
+            });
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn super_admin_transfers_show(
+            &self,
+            group_id: u32,
+            to: AccountId,
+        ) -> Result<SuperAdminTransfer, AZGroupsError> {
+            self.super_admin_transfers
+                .get((group_id, to))
+                .ok_or(AZGroupsError::NotFound("SuperAdminTransfer".to_string()))
+        }
+
+        #[ink(message)]
+        pub fn super_admins_count(&self, group_id: u32) -> u32 {
+            self.group_users_count(group_id, Some(Role::SuperAdmin))
+        }
+
+        // Permissions granted to the user on top of those of their role.
+        #[ink(message)]
+        pub fn user_permissions(&self, group_id: u32, user: AccountId) -> Vec<Permission> {
+            self.permissions_by_user
+                .get((group_id, user))
+                .unwrap_or_default()
+        }
+
+        // Only a super admin can grant permissions, and only to members and admins.
+        // Grants are removed when the user leaves the group or drops below member.
+        #[ink(message)]
+        pub fn user_permissions_update(
+            &mut self,
+            group_id: u32,
+            user: AccountId,
+            permissions: Vec<Permission>,
+        ) -> Result<Vec<Permission>, AZGroupsError> {
+            let user_group_user: GroupUser = self.group_users_show(group_id, user)?;
+            self.authorise_permissions_update(group_id, &user_group_user.role)?;
+            let permissions: Vec<Permission> = AZGroups::dedup_permissions(permissions);
+            if permissions.is_empty() {
+                self.permissions_by_user.remove((group_id, user));
+            } else {
+                self.permissions_by_user
+                    .insert((group_id, user), &permissions);
+            }
+
+            // emit event
+            self.env().emit_event(UserPermissionsUpdate {
+                group_id,
+                user,
+                permissions: permissions.clone(),
+            });
+
+            Ok(permissions)
+        }
+
+        // Only a user who can manage members can approve or reject, and only applicants can be approved or rejected.
+        fn authorise_applicant_review(
+            &self,
+            group_id: u32,
+            caller: AccountId,
+            user: AccountId,
+        ) -> Result<(), AZGroupsError> {
+            self.group_users_show(group_id, caller)?;
+            if !self.has_permission(group_id, caller, Permission::ManageMembers) {
+                return Err(AZGroupsError::Unauthorised);
+            }
+            let user_group_user: GroupUser = self.group_users_show(group_id, user)?;
+            if user_group_user.role != Role::Applicant {
+                return Err(AZGroupsError::UnprocessableEntity(
+                    "Group user is not an applicant".to_string(),
+                ));
+            }
+
+            Ok(())
+        }
+
+        // The inviter must be able to manage invitations and have at least the invited role.
+        fn authorise_invitation(
+            &self,
+            group_id: u32,
+            inviter: AccountId,
+            role: &Role,
+        ) -> Result<(), AZGroupsError> {
+            let inviter_group_user: GroupUser = self.group_users_show(group_id, inviter)?;
+            if !self.has_permission(group_id, inviter, Permission::ManageInvitations)
+                || role.to_int() > inviter_group_user.role.to_int()
+            {
+                return Err(AZGroupsError::Unauthorised);
+            }
+
+            Ok(())
+        }
+
+        fn authorise_permissions_update(
+            &self,
+            group_id: u32,
+            role: &Role,
+        ) -> Result<(), AZGroupsError> {
+            let caller: AccountId = Self::env().caller();
+            let caller_group_user: GroupUser = self.group_users_show(group_id, caller)?;
+            if caller_group_user.role != Role::SuperAdmin {
+                return Err(AZGroupsError::Unauthorised);
+            }
+            if *role != Role::Member && *role != Role::Admin {
+                return Err(AZGroupsError::UnprocessableEntity(
+                    "Permissions can only be assigned to members and admins".to_string(),
+                ));
+            }
+
+            Ok(())
+        }
+
+        fn dedup_permissions(permissions: Vec<Permission>) -> Vec<Permission> {
+            let mut deduped: Vec<Permission> = Vec::new();
+            for permission in permissions {
+                if !deduped.contains(&permission) {
+                    deduped.push(permission);
+                }
+            }
+            deduped
+        }
+
+        // Matches what admins and members could do before permissions were configurable.
+        fn default_role_permissions(role: &Role) -> Vec<Permission> {
+            match role {
+                Role::Admin => [
+                    Permission::ManageMembers,
+                    Permission::ManageInvitations,
+                    Permission::ManageHubRecords,
+                ]
+                .to_vec(),
+                Role::Member => [Permission::ManageHubRecords].to_vec(),
+                _ => Vec::new(),
+            }
+        }
+
+        fn format_group_name(name: String) -> String {
+            name.trim().to_string()
+        }
+
+        // Blank values are stored as None. Lengths are limited in bytes.
+        fn format_profile_field(
+            value: Option<String>,
+            field_name: &str,
+            limit: usize,
+        ) -> Result<Option<String>, AZGroupsError> {
+            let value_formatted: Option<String> = value
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty());
+            if let Some(ref value_unwrapped) = value_formatted {
+                if value_unwrapped.len() > limit {
+                    return Err(AZGroupsError::UnprocessableEntity(format!(
+                        "{field_name} can't be longer than {limit} bytes"
+                    )));
+                }
+            }
+
+            Ok(value_formatted)
+        }
+
+        fn remove_group_user(&mut self, group_id: u32, user: AccountId) {
+            if let Some(group_user) = self.group_users.get((group_id, user)) {
+                self.update_group_users_count(group_id, group_user.role, false);
+            }
+            self.group_users.remove((group_id, user));
+            self.application_notes.remove((group_id, user));
+            self.permissions_by_user.remove((group_id, user));
+            // The last user of the group is moved into the position of the removed user
+            if let Some(position) = self.user_positions_by_group.take((group_id, user)) {
+                let last_position: u32 = self
+                    .users_count_by_group
+                    .get(group_id)
+                    .unwrap_or(1)
+                    .saturating_sub(1);
+                if let Some(last_user) = self.users_by_group.take((group_id, last_position)) {
+                    if position != last_position {
+                        self.users_by_group.insert((group_id, position), &last_user);
+                        self.user_positions_by_group
+                            .insert((group_id, last_user), &position);
+                    }
+                }
+                if last_position == 0 {
+                    self.users_count_by_group.remove(group_id);
+                } else {
+                    self.users_count_by_group.insert(group_id, &last_position);
+                }
+            }
+            // The group the user joined last is moved into the position of the removed group
+            if let Some(position) = self.group_positions_by_user.take((user, group_id)) {
+                let last_position: u32 = self
+                    .groups_count_by_user
+                    .get(user)
+                    .unwrap_or(1)
+                    .saturating_sub(1);
+                if let Some(last_group_id) = self.group_ids_by_user.take((user, last_position)) {
+                    if position != last_position {
+                        self.group_ids_by_user
+                            .insert((user, position), &last_group_id);
+                        self.group_positions_by_user
+                            .insert((user, last_group_id), &position);
+                    }
+                }
+                if last_position == 0 {
+                    self.groups_count_by_user.remove(user);
+                } else {
+                    self.groups_count_by_user.insert(user, &last_position);
+                }
+            }
+        }
+
+        // Stores the group user, keeping the member lists and role counts in sync.
+        fn set_group_user(&mut self, group_id: u32, user: AccountId, group_user: &GroupUser) {
+            match self.group_users.get((group_id, user)) {
+                Some(previous) => self.update_group_users_count(group_id, previous.role, false),
+                None => {
+                    let users_count: u32 = self.users_count_by_group.get(group_id).unwrap_or(0);
+                    self.users_by_group.insert((group_id, users_count), &user);
+                    self.user_positions_by_group
+                        .insert((group_id, user), &users_count);
+                    self.users_count_by_group
+                        .insert(group_id, &users_count.checked_add(1).unwrap());
+                    let groups_count: u32 = self.groups_count_by_user.get(user).unwrap_or(0);
+                    self.group_ids_by_user
+                        .insert((user, groups_count), &group_id);
+                    self.group_positions_by_user
+                        .insert((user, group_id), &groups_count);
+                    self.groups_count_by_user
+                        .insert(user, &groups_count.checked_add(1).unwrap());
+                }
+            }
+            self.update_group_users_count(group_id, group_user.role.clone(), true);
+            self.group_users.insert((group_id, user), group_user);
+            // The application note is only kept while the user is an applicant
+            if group_user.role != Role::Applicant {
+                self.application_notes.remove((group_id, user));
+            }
+            // Granted permissions are only kept while the user is a member or above
+            if group_user.role.to_int() < 2 {
+                self.permissions_by_user.remove((group_id, user));
+            }
+        }
+
+        fn update_group_users_count(&mut self, group_id: u32, role: Role, increase: bool) {
+            let count: u32 = self
+                .group_users_count_by_role
+                .get((group_id, role.clone()))
+                .unwrap_or(0);
+            let new_count: u32 = if increase {
+                count.saturating_add(1)
+            } else {
+                count.saturating_sub(1)
+            };
+            self.group_users_count_by_role
+                .insert((group_id, role), &new_count);
+        }
+
+        fn validate_group_name_presence(name: String) -> Result<(), AZGroupsError> {
+            if name.is_empty() {
+                return Err(AZGroupsError::UnprocessableEntity(
+                    "Name can't be blank".to_string(),
+                ));
+            };
+
+            Ok(())
+        }
+
+        fn validate_group_name_uniqueness(&self, key: String) -> Result<(), AZGroupsError> {
+            if self.group_id_by_name.get(key).is_some() {
+                return Err(AZGroupsError::UnprocessableEntity(
+                    "Group has already been taken".to_string(),
+                ));
+            }
+
+            Ok(())
+        }
+
+        // Only accounts without a group user, or applicants, can be invited.
+        fn validate_invitable(group_user: &Option<GroupUser>) -> Result<(), AZGroupsError> {
+            if let Some(group_user) = group_user {
+                if group_user.role != Role::Applicant {
+                    return Err(AZGroupsError::UnprocessableEntity(
+                        "Group user has already been taken".to_string(),
+                    ));
+                }
+            }
+
+            Ok(())
+        }
+
+        fn validate_not_closed(group: &Group) -> Result<(), AZGroupsError> {
+            if group.membership_mode == MembershipMode::Closed {
+                return Err(AZGroupsError::UnprocessableEntity(
+                    "Group is closed".to_string(),
+                ));
+            }
+
+            Ok(())
+        }
+
+        fn validate_super_admin_remains(&self, group_id: u32) -> Result<(), AZGroupsError> {
+            if self.super_admins_count(group_id) <= 1 {
+                return Err(AZGroupsError::UnprocessableEntity(
+                    "Group must have a super admin".to_string(),
+                ));
+            }
+
+            Ok(())
+        }
+    }
+
+    impl AZGroupsMembership for AZGroups {
+        #[ink(message)]
+        fn validate_membership(
+            &self,
+            group_id: u32,
+            user: AccountId,
+        ) -> Result<Role, AZGroupsError> {
+            let group: Group = self.groups_show(group_id)?;
+            if !group.enabled {
+                return Err(AZGroupsError::GroupDisabled);
+            }
+            let group_user: GroupUser = self.group_users_show(group_id, user)?;
+            if group_user.role.to_int() < 2 {
+                return Err(AZGroupsError::NotAMember);
+            }
+
+            Ok(group_user.role)
+        }
+
+        // Banned users and applicants have no permissions.
+        // Whether the group is enabled is not considered, so that a disabled group can still be managed.
+        #[ink(message)]
+        fn has_permission(&self, group_id: u32, user: AccountId, permission: Permission) -> bool {
+            let Some(group_user) = self.group_users.get((group_id, user)) else {
+                return false;
+            };
+            if group_user.role.to_int() < 2 {
+                return false;
+            }
+
+            self.role_permissions(group_id, group_user.role)
+                .contains(&permission)
+                || self.user_permissions(group_id, user).contains(&permission)
+        }
+    }
 
-            Ok(())
-        }
-    }
-
     #[cfg(test)]
     mod tests {
         use super::*;
         use ink::env::{test::DefaultAccounts, DefaultEnvironment};
 
-        // === CONSTANTS ===
-        const MOCK_GROUP_NAME: &str = "The Next Wave";
+        // === CONSTANTS ===
+        const MOCK_GROUP_NAME: &str = "The Next Wave";
+
+        // === HELPERS ===
+        fn init() -> (DefaultAccounts<DefaultEnvironment>, AZGroups) {
+            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            let az_groups = AZGroups::new();
+            (accounts, az_groups)
+        }
+
+        // === TEST QUERIES ===
+        #[ink::test]
+        fn test_config() {
+            let (_accounts, mut az_groups) = init();
+            // * it returns the groups total
+            assert_eq!(az_groups.config().groups_total, 0);
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            assert_eq!(az_groups.config().groups_total, 1);
+        }
+
+        #[ink::test]
+        fn test_group_users_count() {
+            let (accounts, mut az_groups) = init();
+            // when group does not exist
+            // * it returns zero
+            assert_eq!(az_groups.group_users_count(0, None), 0);
+            // when group exists
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.group_users_create(0, None).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            az_groups.group_users_create(0, None).unwrap();
+            // = when role_filter is absent
+            // = * it returns the number of users in the group
+            assert_eq!(az_groups.group_users_count(0, None), 3);
+            // = when role_filter is present
+            // = * it returns the number of users with that role
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Applicant)), 2);
+            assert_eq!(az_groups.group_users_count(0, Some(Role::SuperAdmin)), 1);
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Member)), 0);
+            // = when a role is updated
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Member)
+                .unwrap();
+            // = * it moves the user to the new role's count
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Applicant)), 1);
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Member)), 1);
+            // = when a user is destroyed
+            az_groups.group_users_destroy(0, accounts.django).unwrap();
+            // = * it decreases the counts
+            assert_eq!(az_groups.group_users_count(0, None), 2);
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Applicant)), 0);
+        }
+
+        #[ink::test]
+        fn test_group_users_index() {
+            let (accounts, mut az_groups) = init();
+            // when group does not exist
+            // * it returns an empty list
+            assert!(az_groups.group_users_index(0, None, 0, 2).is_empty());
+            // when group exists
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.group_users_create(0, None).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            az_groups.group_users_create(0, None).unwrap();
+            // = when role_filter is absent
+            // = * it returns the users and roles for the page in the order they joined
+            assert_eq!(
+                az_groups.group_users_index(0, None, 0, 2),
+                [
+                    (accounts.bob, Role::SuperAdmin),
+                    (accounts.charlie, Role::Applicant)
+                ]
+            );
+            assert_eq!(
+                az_groups.group_users_index(0, None, 1, 2),
+                [(accounts.django, Role::Applicant)]
+            );
+            // = when role_filter is present
+            // = * it only returns users with that role
+            assert_eq!(
+                az_groups.group_users_index(0, Some(Role::Applicant), 0, 2),
+                [
+                    (accounts.charlie, Role::Applicant),
+                    (accounts.django, Role::Applicant)
+                ]
+            );
+            // = when a user is destroyed
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups.group_users_destroy(0, accounts.charlie).unwrap();
+            // = * it is removed from the list
+            assert_eq!(
+                az_groups.group_users_index(0, Some(Role::Applicant), 0, 2),
+                [(accounts.django, Role::Applicant)]
+            );
+            // = * the last user to join takes its place
+            assert_eq!(
+                az_groups.group_users_index(0, None, 0, 2),
+                [
+                    (accounts.bob, Role::SuperAdmin),
+                    (accounts.django, Role::Applicant)
+                ]
+            );
+            assert_eq!(az_groups.group_users_count(0, None), 2);
+        }
+
+        #[ink::test]
+        fn test_groups_by_user() {
+            let (accounts, mut az_groups) = init();
+            // when user is not in any groups
+            // * it returns an empty list
+            assert!(az_groups.groups_by_user(accounts.charlie, 0, 2).is_empty());
+            // when user is in groups
+            for name in ["A", "B"] {
+                az_groups
+                    .groups_create(name.to_string(), None, None, None, None)
+                    .unwrap();
+            }
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups
+                .groups_create("C".to_string(), None, None, None, None)
+                .unwrap();
+            az_groups.group_users_create(1, None).unwrap();
+            az_groups.group_users_create(0, None).unwrap();
+            // * it returns the groups and roles for the page in the order they were joined
+            let mut result: Vec<(Group, Role)> = az_groups.groups_by_user(accounts.charlie, 0, 2);
+            assert_eq!(
+                result,
+                [
+                    (az_groups.groups.get(2).unwrap(), Role::SuperAdmin),
+                    (az_groups.groups.get(1).unwrap(), Role::Applicant)
+                ]
+            );
+            result = az_groups.groups_by_user(accounts.charlie, 1, 2);
+            assert_eq!(
+                result,
+                [(az_groups.groups.get(0).unwrap(), Role::Applicant)]
+            );
+            // * it reflects role updates
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Member)
+                .unwrap();
+            result = az_groups.groups_by_user(accounts.charlie, 1, 2);
+            assert_eq!(result, [(az_groups.groups.get(0).unwrap(), Role::Member)]);
+            // when user leaves or is removed from a group
+            az_groups.group_users_destroy(1, accounts.charlie).unwrap();
+            // * it moves the group the user joined last into its place
+            result = az_groups.groups_by_user(accounts.charlie, 0, 2);
+            assert_eq!(
+                result,
+                [
+                    (az_groups.groups.get(2).unwrap(), Role::SuperAdmin),
+                    (az_groups.groups.get(0).unwrap(), Role::Member)
+                ]
+            );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.group_users_destroy(0, accounts.charlie).unwrap();
+            // * it removes the group from the list
+            result = az_groups.groups_by_user(accounts.charlie, 0, 2);
+            assert_eq!(
+                result,
+                [(az_groups.groups.get(2).unwrap(), Role::SuperAdmin)]
+            );
+        }
+
+        #[ink::test]
+        fn test_groups_index() {
+            let (_accounts, mut az_groups) = init();
+            // when there are no groups
+            // * it returns an empty list
+            assert!(az_groups.groups_index(false, 0, 2).is_empty());
+            // when there are groups
+            for name in ["A", "B", "C"] {
+                az_groups
+                    .groups_create(name.to_string(), None, None, None, None)
+                    .unwrap();
+            }
+            az_groups
+                .groups_update(
+                    1,
+                    "B".to_string(),
+                    false,
+                    MembershipMode::Approval,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            // = when enabled_only is false
+            // = * it returns the groups for the page in order of id
+            let mut result: Vec<Group> = az_groups.groups_index(false, 0, 2);
+            assert_eq!(
+                result.iter().map(|group| group.id).collect::<Vec<u32>>(),
+                [0, 1]
+            );
+            result = az_groups.groups_index(false, 1, 2);
+            assert_eq!(
+                result.iter().map(|group| group.id).collect::<Vec<u32>>(),
+                [2]
+            );
+            // = when enabled_only is true
+            // = * it skips disabled groups
+            result = az_groups.groups_index(true, 0, 2);
+            assert_eq!(
+                result.iter().map(|group| group.id).collect::<Vec<u32>>(),
+                [0, 2]
+            );
+            // = when page is out of range
+            // = * it returns an empty list
+            assert!(az_groups.groups_index(true, 1, 2).is_empty());
+        }
+
+        #[ink::test]
+        fn test_role_permissions() {
+            let (_accounts, mut az_groups) = init();
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // when role is super admin
+            // * it returns every permission
+            assert_eq!(az_groups.role_permissions(0, Role::SuperAdmin).len(), 4);
+            // when role permissions have not been set
+            // * it returns the defaults for the role
+            assert_eq!(
+                az_groups.role_permissions(0, Role::Admin),
+                [
+                    Permission::ManageMembers,
+                    Permission::ManageInvitations,
+                    Permission::ManageHubRecords
+                ]
+            );
+            assert_eq!(
+                az_groups.role_permissions(0, Role::Member),
+                [Permission::ManageHubRecords]
+            );
+            assert!(az_groups.role_permissions(0, Role::Applicant).is_empty());
+            // when role permissions have been set
+            az_groups
+                .role_permissions_update(0, Role::Member, Vec::new())
+                .unwrap();
+            // * it returns the set permissions
+            assert!(az_groups.role_permissions(0, Role::Member).is_empty());
+        }
+
+        #[ink::test]
+        fn test_super_admins_count() {
+            let (accounts, mut az_groups) = init();
+            // when group does not exist
+            // * it returns zero
+            assert_eq!(az_groups.super_admins_count(0), 0);
+            // when group exists
+            // * it returns the number of super admins
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            assert_eq!(az_groups.super_admins_count(0), 1);
+            az_groups
+                .invitations_create(0, accounts.charlie, Role::SuperAdmin, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.invitations_accept(0).unwrap();
+            assert_eq!(az_groups.super_admins_count(0), 2);
+        }
+
+        // === TEST HANDLES ===
+        #[ink::test]
+        fn test_group_users_approve() {
+            let (accounts, mut az_groups) = init();
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups
+                .group_users_create(0, Some("Hello".to_string()))
+                .unwrap();
+            // when caller is not an admin
+            // * it raises an error
+            let mut result = az_groups.group_users_approve(0, accounts.charlie);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // when caller is an admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            // = when user does not have a group user
+            // = * it raises an error
+            result = az_groups.group_users_approve(0, accounts.django);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+            );
+            // = when user is an applicant
+            // = * it makes the user a member
+            result = az_groups.group_users_approve(0, accounts.charlie);
+            assert_eq!(result.unwrap().role, Role::Member);
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Member)), 1);
+            // = * it removes the application note
+            assert!(az_groups.application_note(0, accounts.charlie).is_none());
+            // = when user is not an applicant
+            // = * it raises an error
+            result = az_groups.group_users_approve(0, accounts.charlie);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group user is not an applicant".to_string()
+                ))
+            );
+        }
+
+        #[ink::test]
+        fn test_group_users_create() {
+            let (accounts, mut az_groups) = init();
+            let group_name: String = MOCK_GROUP_NAME.to_string();
+            // when group with id does not exist
+            // * it raises an error
+            let mut result = az_groups.group_users_create(0, None);
+            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
+            // when group with id exists
+            az_groups
+                .groups_create(group_name, None, None, None, None)
+                .unwrap();
+            // = when GroupUser exists
+            result = az_groups.group_users_create(0, None);
+            // = * it raises an error
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group user has already been taken".to_string()
+                ))
+            );
+            // = when GroupUser doesn't exist
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            // == when group is invite only
+            let mut group: Group = az_groups.groups.get(0).unwrap();
+            group.membership_mode = MembershipMode::InviteOnly;
+            az_groups.groups.insert(0, &group);
+            // == * it raises an error
+            result = az_groups.group_users_create(0, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group is invite only".to_string()
+                ))
+            );
+            // == when group is closed
+            group.membership_mode = MembershipMode::Closed;
+            az_groups.groups.insert(0, &group);
+            // == * it raises an error
+            result = az_groups.group_users_create(0, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group is closed".to_string()
+                ))
+            );
+            // == when group is open
+            group.membership_mode = MembershipMode::Open;
+            az_groups.groups.insert(0, &group);
+            // == * it creates the group user with the role member
+            result = az_groups.group_users_create(0, Some("Hello".to_string()));
+            assert_eq!(result.unwrap().role, Role::Member);
+            // == * it does not store the note
+            assert!(az_groups.application_note(0, accounts.alice).is_none());
+            az_groups.group_users_destroy(0, accounts.alice).unwrap();
+            // == when group requires approval
+            group.membership_mode = MembershipMode::Approval;
+            az_groups.groups.insert(0, &group);
+            // === when note is too long
+            // === * it raises an error
+            result = az_groups.group_users_create(0, Some("a".repeat(281)));
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Note can't be longer than 280 bytes".to_string()
+                ))
+            );
+            // === when note is valid
+            // === * it creates the group user with the role applicant
+            result = az_groups.group_users_create(0, Some(" Hello ".to_string()));
+            assert_eq!(result.unwrap().role, Role::Applicant);
+            // === * it stores the formatted note
+            assert_eq!(
+                az_groups.application_note(0, accounts.alice),
+                Some("Hello".to_string())
+            );
+            // === when user was rejected
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups.groups_update_application_cooldown(0, 10).unwrap();
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5);
+            az_groups.group_users_reject(0, accounts.alice).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
+            // ==== when application cooldown has not passed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(14);
+            // ==== * it raises an error
+            result = az_groups.group_users_create(0, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Application cooldown has not passed".to_string()
+                ))
+            );
+            // ==== when application cooldown has passed
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(15);
+            // ==== * it creates the group user
+            result = az_groups.group_users_create(0, None);
+            assert_eq!(result.unwrap().role, Role::Applicant);
+            // ==== * it clears the rejection
+            assert!(az_groups.rejected_at.get((0, accounts.alice)).is_none());
+        }
+
+        #[ink::test]
+        fn test_group_users_destroy() {
+            let (accounts, mut az_groups) = init();
+            let group_name: String = MOCK_GROUP_NAME.to_string();
+            //  when group with key exists
+            az_groups
+                .groups_create(group_name.clone(), None, None, None, None)
+                .unwrap();
+            // = when caller does not have a group user for team
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            let mut result = az_groups.group_users_destroy(0, accounts.bob);
+            // = * it raises an error
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+            );
+            // = when caller has a group user for team
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            // == when user does not have a group user for team
+            result = az_groups.group_users_destroy(0, accounts.charlie);
+            // == * it raises an error
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+            );
+            // == when user has a group user for team
+            // === when caller equals user
+            // ==== when role is super admin
+            // ===== when user is the last super admin
+            // ===== * it raises an error
+            result = az_groups.group_users_destroy(0, accounts.bob);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group must have a super admin".to_string()
+                ))
+            );
+            // ===== when group has another super admin
+            az_groups
+                .group_users
+                .insert((0, accounts.django), &GroupUser { role: Role::Admin });
+            az_groups
+                .group_users_update(0, accounts.django, Role::SuperAdmin)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            // ===== * it destroys UserGroup
+            az_groups.group_users_destroy(0, accounts.django).unwrap();
+            assert!(az_groups.group_users.get((0, accounts.django)).is_none());
+            assert_eq!(az_groups.super_admins_count(0), 1);
+            // ==== when role is not super admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // ===== when role is not banned
+            az_groups.group_users_create(0, None).unwrap();
+            // ===== * it destroys UserGroup
+            az_groups.group_users_destroy(0, accounts.charlie).unwrap();
+            assert!(az_groups.group_users.get((0, accounts.charlie)).is_none());
+            // ===== when role is banned
+            az_groups.group_users_create(0, None).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Banned)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // ===== * it raises an error
+            result = az_groups.group_users_destroy(0, accounts.charlie);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // === when caller does not equal user
+            // ==== when caller role is less than 3 (less than admin)
+            // ==== * it raises an error
+            result = az_groups.group_users_destroy(0, accounts.bob);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // ==== when caller role is greater than or equal to 3
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Admin)
+                .unwrap();
+            // ===== when caller's role is less than user's role
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // ===== * it raises an error
+            result = az_groups.group_users_destroy(0, accounts.bob);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // ===== when caller's role is greater than or equal to user's role
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::SuperAdmin)
+                .unwrap();
+            // ===== * it destroys UserGroup
+            az_groups.group_users_destroy(0, accounts.charlie).unwrap();
+            assert!(az_groups.group_users.get((0, accounts.charlie)).is_none());
+        }
+
+        #[ink::test]
+        fn test_group_users_reject() {
+            let (accounts, mut az_groups) = init();
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups
+                .group_users_create(0, Some("Hello".to_string()))
+                .unwrap();
+            // when caller is not an admin
+            // * it raises an error
+            let mut result = az_groups.group_users_reject(0, accounts.charlie);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // when caller is an admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            // = when user is not an applicant
+            // = * it raises an error
+            result = az_groups.group_users_reject(0, accounts.bob);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group user is not an applicant".to_string()
+                ))
+            );
+            // = when user is an applicant
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(7);
+            az_groups.group_users_reject(0, accounts.charlie).unwrap();
+            // = * it removes the group user and application note
+            assert!(az_groups.group_users.get((0, accounts.charlie)).is_none());
+            assert!(az_groups.application_note(0, accounts.charlie).is_none());
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Applicant)), 0);
+            // = * it records when the user was rejected
+            assert_eq!(az_groups.rejected_at.get((0, accounts.charlie)), Some(7));
+        }
+
+        #[ink::test]
+        fn test_group_users_update() {
+            let (accounts, mut az_groups) = init();
+            let group_name: String = MOCK_GROUP_NAME.to_string();
+            // = when group with key exists
+            az_groups
+                .groups_create(group_name, None, None, None, None)
+                .unwrap();
+            // == when caller equals user
+            // == * it raises an error
+            let mut result = az_groups.group_users_update(0, accounts.bob, Role::SuperAdmin);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // == when caller is different to user
+            // === when caller does not have a group user for team
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            result = az_groups.group_users_update(0, accounts.bob, Role::SuperAdmin);
+            // === * it raises an error
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+            );
+            // === when caller has a group user for team
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            // ==== when caller's role is less than 3
+            let mut caller_group_user: GroupUser =
+                az_groups.group_users.get((0, accounts.bob)).unwrap();
+            caller_group_user.role = Role::Member;
+            az_groups
+                .group_users
+                .insert((0, accounts.bob), &caller_group_user);
+            // ==== * it raises an error
+            result = az_groups.group_users_update(0, accounts.bob, Role::Member);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // ==== when caller's role is 3 or more
+            caller_group_user.role = Role::Admin;
+            az_groups
+                .group_users
+                .insert((0, accounts.bob), &caller_group_user);
+            // ===== when user does not have a group user for team
+            result = az_groups.group_users_update(0, accounts.charlie, Role::SuperAdmin);
+            // ===== * it raises an error
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+            );
+            // ===== when user has a role with team
+            // ====== when caller's role is less than user's role
+            let mut user_group_user: GroupUser = GroupUser {
+                role: Role::SuperAdmin,
+            };
+            az_groups
+                .group_users
+                .insert((0, accounts.charlie), &user_group_user);
+            // ====== * it raises an error
+            result = az_groups.group_users_update(0, accounts.charlie, Role::SuperAdmin);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // ====== when caller's role is greater than or equal to user's role
+            user_group_user = GroupUser { role: Role::Admin };
+            az_groups
+                .group_users
+                .insert((0, accounts.charlie), &user_group_user);
+            // ======= when new role is less than or equal to caller's role
+            // ======= * it updates the user's role
+            result = az_groups.group_users_update(0, accounts.charlie, Role::Admin);
+            assert_eq!(result.unwrap().role, Role::Admin);
+            // ======= when new role is greater than caller's role
+            // ======= * it raises an error
+            result = az_groups.group_users_update(0, accounts.charlie, Role::SuperAdmin);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+        }
+
+        #[ink::test]
+        fn test_groups_create() {
+            let (accounts, mut az_groups) = init();
+            let group_name: String = MOCK_GROUP_NAME.to_string();
+            let key: String = group_name.to_lowercase();
+            // when group with key does not exist
+            // * it creates the group with the supplied name
+            // * it sets the group to enabled
+            // * it returns the created group
+            let mut result = az_groups.groups_create(group_name.clone(), None, None, None, None);
+            let group = result.unwrap();
+            assert_eq!(group.name, group_name);
+            assert_eq!(group.enabled, true);
+            // * it creates and sets a new GroupUser with the caller as super admin
+            let group_user: GroupUser = az_groups.group_users.get((0, accounts.bob)).unwrap();
+            assert_eq!(group_user.role, Role::SuperAdmin);
+            // * it sets the group_id_by_name
+            assert_eq!(az_groups.group_id_by_name.get(key.clone()).unwrap(), 0);
+            // * it increases the groups total by one
+            assert_eq!(az_groups.groups_total, 1);
+            // when group with key already exists
+            // * it raises an error
+            result = az_groups.groups_create(key, None, None, None, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group has already been taken".to_string()
+                ))
+            );
+            // when groups_total is u32 max
+            az_groups.groups_total = u32::MAX;
+            // * it raises an error
+            result = az_groups.groups_create("XXXX".to_string(), None, None, None, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group limit reached".to_string()
+                ))
+            );
+            // when group_name is blank
+            // * it raises an error
+            result = az_groups.groups_create(" ".to_string(), None, None, None, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Name can't be blank".to_string()
+                ))
+            );
+            // when a profile field is too long
+            // * it raises an error
+            az_groups.groups_total = 1;
+            result = az_groups.groups_create(
+                "XXXX".to_string(),
+                Some("a".repeat(501)),
+                None,
+                None,
+                None,
+            );
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Description can't be longer than 500 bytes".to_string()
+                ))
+            );
+            result =
+                az_groups.groups_create("XXXX".to_string(), None, None, None, Some("a".repeat(65)));
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "AZERO.ID can't be longer than 64 bytes".to_string()
+                ))
+            );
+            // when profile fields are valid
+            // * it stores the formatted profile fields
+            result = az_groups.groups_create(
+                "XXXX".to_string(),
+                Some(" We build things ".to_string()),
+                Some("https://thenextwave.xyz".to_string()),
+                Some(" ".to_string()),
+                Some("thenextwave.azero".to_string()),
+            );
+            let group: Group = result.unwrap();
+            assert_eq!(group.description, Some("We build things".to_string()));
+            assert_eq!(group.website, Some("https://thenextwave.xyz".to_string()));
+            assert_eq!(group.logo_url, None);
+            assert_eq!(group.azero_id, Some("thenextwave.azero".to_string()));
+            assert_eq!(az_groups.groups_show(1).unwrap(), group);
+        }
+
+        #[ink::test]
+        fn test_groups_find_by_name() {
+            let (_accounts, mut az_groups) = init();
+            let group_name: String = MOCK_GROUP_NAME.to_string();
+            // when group with name does not exist
+            // * it raises an error
+            let mut result = az_groups.groups_find_by_name(group_name.clone());
+            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
+            // when group with name exists
+            az_groups
+                .groups_create(group_name.clone(), None, None, None, None)
+                .unwrap();
+            // = when name with no matching key is provided
+            // = * it raises an error
+            result = az_groups.groups_find_by_name("asdf".to_string());
+            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
+            // = when name with a matching key is provided (case insensitive)
+            // = * it returns the group
+            result = az_groups.groups_find_by_name(group_name.to_uppercase());
+            assert_eq!(result.unwrap(), az_groups.groups.get(0).unwrap());
+        }
 
-        // === HELPERS ===
-        fn init() -> (DefaultAccounts<DefaultEnvironment>, AZGroups) {
-            let accounts = ink::env::test::default_accounts::<ink::env::DefaultEnvironment>();
+        #[ink::test]
+        fn test_groups_update() {
+            let (accounts, mut az_groups) = init();
+            let group_name: String = MOCK_GROUP_NAME.to_string();
+            let key: String = group_name.to_lowercase();
+            // when group with key does not exist
+            // * it raises an error
+            let mut result = az_groups.groups_update(
+                0,
+                group_name.clone(),
+                true,
+                MembershipMode::Approval,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
+            // when group with key exists
+            az_groups
+                .groups_create(group_name.clone(), None, None, None, None)
+                .unwrap();
+            // = when caller is not part of group
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // = * it raises an error
+            result = az_groups.groups_update(
+                0,
+                group_name.clone(),
+                true,
+                MembershipMode::Approval,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+            );
+            // = when caller is part of group
+            az_groups.group_users_create(0, None).unwrap();
+            // == when caller is not a super admin
+            // == * it raises an error
+            result = az_groups.groups_update(
+                0,
+                group_name.clone(),
+                true,
+                MembershipMode::Approval,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // == when caller is a super admin
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            let az_groups = AZGroups::new();
-            (accounts, az_groups)
+            // === when new_name is present
+            // ==== when new_name is empty blank
+            // ==== * it raises an error
+            result = az_groups.groups_update(
+                0,
+                " ".to_string(),
+                false,
+                MembershipMode::Approval,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Name can't be blank".to_string()
+                ))
+            );
+            // ==== when new_name is available
+            // ==== * it updates the group
+            let mut new_name: String = "King Kong".to_string();
+            result = az_groups.groups_update(
+                0,
+                new_name.clone(),
+                false,
+                MembershipMode::InviteOnly,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(
+                result.unwrap(),
+                Group {
+                    id: 0,
+                    name: new_name.clone(),
+                    enabled: false,
+                    membership_mode: MembershipMode::InviteOnly,
+                    description: None,
+                    website: None,
+                    logo_url: None,
+                    azero_id: None
+                }
+            );
+            // ==== * it removes the old group_id_by_name map
+            assert!(az_groups.group_id_by_name.get(key).is_none());
+            // ==== * it create the new group_id_by_name map
+            assert_eq!(
+                az_groups
+                    .group_id_by_name
+                    .get(new_name.to_lowercase())
+                    .unwrap(),
+                0
+            );
+            // ==== when new_name is taken
+            // ===== when new_name's key is the same as the original key
+            new_name = new_name.to_uppercase() + " ";
+            result = az_groups.groups_update(
+                0,
+                new_name.clone(),
+                true,
+                MembershipMode::Approval,
+                None,
+                None,
+                None,
+                None,
+            );
+            // ===== * it updates
+            assert_eq!(
+                result.unwrap(),
+                Group {
+                    id: 0,
+                    name: AZGroups::format_group_name(new_name),
+                    enabled: true,
+                    membership_mode: MembershipMode::Approval,
+                    description: None,
+                    website: None,
+                    logo_url: None,
+                    azero_id: None
+                }
+            );
+            // === when a profile field is too long
+            // === * it raises an error
+            result = az_groups.groups_update(
+                0,
+                "King Kong".to_string(),
+                true,
+                MembershipMode::Approval,
+                None,
+                None,
+                Some("a".repeat(257)),
+                None,
+            );
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Logo url can't be longer than 256 bytes".to_string()
+                ))
+            );
+            // === when profile fields are valid
+            // === * it updates the profile fields
+            result = az_groups.groups_update(
+                0,
+                "King Kong".to_string(),
+                true,
+                MembershipMode::Approval,
+                Some("We build things".to_string()),
+                Some("https://thenextwave.xyz".to_string()),
+                Some("https://thenextwave.xyz/logo.png".to_string()),
+                Some("thenextwave.azero".to_string()),
+            );
+            let group: Group = result.unwrap();
+            assert_eq!(group.description, Some("We build things".to_string()));
+            assert_eq!(group.website, Some("https://thenextwave.xyz".to_string()));
+            assert_eq!(
+                group.logo_url,
+                Some("https://thenextwave.xyz/logo.png".to_string())
+            );
+            assert_eq!(group.azero_id, Some("thenextwave.azero".to_string()));
+            // ===== when new_name's key is different from the original key
+            az_groups.group_id_by_name.insert("a".to_string(), &1);
+            result = az_groups.groups_update(
+                0,
+                "A".to_string(),
+                true,
+                MembershipMode::Approval,
+                None,
+                None,
+                None,
+                None,
+            );
+            // ===== * it raises an error
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group has already been taken".to_string()
+                ))
+            );
         }
 
-        // === TEST HANDLES ===
         #[ink::test]
-        fn test_group_users_create() {
+        fn test_groups_update_application_cooldown() {
             let (accounts, mut az_groups) = init();
-            let group_name: String = MOCK_GROUP_NAME.to_string();
-            // when group with id does not exist
+            // when group does not exist
             // * it raises an error
-            let mut result = az_groups.group_users_create(0);
+            let mut result = az_groups.groups_update_application_cooldown(0, 10);
             assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
-            // when group with id exists
-            az_groups.groups_create(group_name).unwrap();
-            // = when GroupUser exists
-            result = az_groups.group_users_create(0);
+            // when group exists
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // = when caller is not a super admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.group_users_create(0, None).unwrap();
+            // = * it raises an error
+            result = az_groups.groups_update_application_cooldown(0, 10);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // = when caller is a super admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            // = * it updates the application cooldown
+            result = az_groups.groups_update_application_cooldown(0, 10);
+            assert_eq!(result.unwrap(), 10);
+            assert_eq!(az_groups.application_cooldown(0), 10);
+        }
+
+        #[ink::test]
+        fn test_invitations_accept() {
+            let (accounts, mut az_groups) = init();
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // when invitation does not exist
+            // * it raises an error
+            let mut result = az_groups.invitations_accept(0);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("Invitation".to_string()))
+            );
+            // when invitation exists
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .invitations_create(0, accounts.charlie, Role::Admin, Some(10))
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // = when group is closed
+            let mut group: Group = az_groups.groups.get(0).unwrap();
+            group.membership_mode = MembershipMode::Closed;
+            az_groups.groups.insert(0, &group);
+            // = * it raises an error
+            result = az_groups.invitations_accept(0);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group is closed".to_string()
+                ))
+            );
+            group.membership_mode = MembershipMode::Approval;
+            az_groups.groups.insert(0, &group);
+            // = when invitation has expired
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(10);
             // = * it raises an error
+            result = az_groups.invitations_accept(0);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Invitation has expired".to_string()
+                ))
+            );
+            // = when invitation has not expired
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(9);
+            // == when invitee has been banned since the invitation
+            az_groups.group_users_create(0, None).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Banned)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // == * it raises an error
+            result = az_groups.invitations_accept(0);
             assert_eq!(
                 result,
                 Err(AZGroupsError::UnprocessableEntity(
                     "Group user has already been taken".to_string()
                 ))
             );
-            // = when GroupUser doesn't exist
-            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.alice);
-            // = * it creates the group user with the role applicant
-            result = az_groups.group_users_create(0);
-            assert_eq!(result.unwrap().role, Role::Applicant);
+            // == when invitee is an applicant
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Applicant)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // == * it sets the group user to the invited role
+            result = az_groups.invitations_accept(0);
+            assert_eq!(result.unwrap().role, Role::Admin);
+            assert_eq!(
+                az_groups
+                    .group_users_show(0, accounts.charlie)
+                    .unwrap()
+                    .role,
+                Role::Admin
+            );
+            assert_eq!(az_groups.group_users_count(0, Some(Role::Applicant)), 0);
+            // == * it removes the invitation
+            assert!(az_groups.invitations.get((0, accounts.charlie)).is_none());
+            // == when invitee does not have a group user
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .invitations_create(0, accounts.django, Role::Member, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            // == * it creates the group user with the invited role
+            result = az_groups.invitations_accept(0);
+            assert_eq!(result.unwrap().role, Role::Member);
+            assert_eq!(
+                az_groups.groups_by_user(accounts.django, 0, 1)[0].1,
+                Role::Member
+            );
+            // == when inviter can no longer invite to the role
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups
+                .invitations_create(0, accounts.eve, Role::Admin, None)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Member)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.eve);
+            // == * it raises an error
+            result = az_groups.invitations_accept(0);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Inviter can no longer invite to this role".to_string()
+                ))
+            );
+            assert!(az_groups.group_users.get((0, accounts.eve)).is_none());
         }
 
         #[ink::test]
-        fn test_group_users_destroy() {
+        fn test_invitations_create() {
             let (accounts, mut az_groups) = init();
-            let group_name: String = MOCK_GROUP_NAME.to_string();
-            //  when group with key exists
-            az_groups.groups_create(group_name.clone()).unwrap();
-            // = when caller does not have a group user for team
+            // when group does not exist
+            // * it raises an error
+            let mut result = az_groups.invitations_create(0, accounts.charlie, Role::Member, None);
+            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
+            // when group exists
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // = when caller does not have a group user
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            let mut result = az_groups.group_users_destroy(0, accounts.bob);
             // = * it raises an error
+            result = az_groups.invitations_create(0, accounts.django, Role::Member, None);
             assert_eq!(
                 result,
                 Err(AZGroupsError::NotFound("GroupUser".to_string()))
             );
-            // = when caller has a group user for team
+            // = when caller's role is less than admin
+            az_groups.group_users_create(0, None).unwrap();
+            // = * it raises an error
+            result = az_groups.invitations_create(0, accounts.django, Role::Member, None);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // = when caller is an admin
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            // == when user does not have a group user for team
-            result = az_groups.group_users_destroy(0, accounts.charlie);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Admin)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // == when role is greater than caller's role
+            // == * it raises an error
+            result = az_groups.invitations_create(0, accounts.django, Role::SuperAdmin, None);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // == when role is less than member
             // == * it raises an error
+            result = az_groups.invitations_create(0, accounts.django, Role::Applicant, None);
             assert_eq!(
                 result,
-                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Role must be member or above".to_string()
+                ))
             );
-            // == when user has a group user for team
-            // === when caller equals user
-            // ==== when role is super admin
-            // ==== * it raises an error
-            result = az_groups.group_users_destroy(0, accounts.bob);
-            assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // ==== when role is not super admin
+            // == when group is closed
+            let mut group: Group = az_groups.groups.get(0).unwrap();
+            group.membership_mode = MembershipMode::Closed;
+            az_groups.groups.insert(0, &group);
+            // == * it raises an error
+            result = az_groups.invitations_create(0, accounts.django, Role::Member, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group is closed".to_string()
+                ))
+            );
+            group.membership_mode = MembershipMode::InviteOnly;
+            az_groups.groups.insert(0, &group);
+            // == when expiry is not in the future
+            ink::env::test::set_block_timestamp::<ink::env::DefaultEnvironment>(5);
+            // == * it raises an error
+            result = az_groups.invitations_create(0, accounts.django, Role::Member, Some(5));
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Expiry must be in the future".to_string()
+                ))
+            );
+            // == when user is already a member
+            // == * it raises an error
+            result = az_groups.invitations_create(0, accounts.bob, Role::Member, None);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Group user has already been taken".to_string()
+                ))
+            );
+            // == when params are valid
+            // == * it stores and returns the invitation
+            result = az_groups.invitations_create(0, accounts.django, Role::Admin, Some(6));
+            let invitation: Invitation = result.unwrap();
+            assert_eq!(
+                invitation,
+                Invitation {
+                    group_id: 0,
+                    user: accounts.django,
+                    role: Role::Admin,
+                    inviter: accounts.charlie,
+                    expires_at: Some(6),
+                }
+            );
+            assert_eq!(
+                az_groups.invitations_show(0, accounts.django).unwrap(),
+                invitation
+            );
+            // == when user already has an invitation
+            // == * it replaces the invitation
+            result = az_groups.invitations_create(0, accounts.django, Role::Member, None);
+            assert_eq!(
+                az_groups.invitations_show(0, accounts.django).unwrap(),
+                result.unwrap()
+            );
+        }
+
+        #[ink::test]
+        fn test_invitations_destroy() {
+            let (accounts, mut az_groups) = init();
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // when invitation does not exist
+            // * it raises an error
+            let mut result = az_groups.invitations_destroy(0, accounts.django);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("Invitation".to_string()))
+            );
+            // when invitation exists
+            az_groups
+                .invitations_create(0, accounts.django, Role::SuperAdmin, None)
+                .unwrap();
+            // = when caller is not the invitee
+            // == when caller does not have a group user
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            // ===== when role is not banned
-            az_groups.group_users_create(0).unwrap();
-            // ===== * it destroys UserGroup
-            az_groups.group_users_destroy(0, accounts.charlie).unwrap();
-            assert!(az_groups.group_users.get((0, accounts.charlie)).is_none());
-            // ===== when role is banned
-            az_groups.group_users_create(0).unwrap();
+            // == * it raises an error
+            result = az_groups.invitations_destroy(0, accounts.django);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+            );
+            // == when caller's role is less than the invited role
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
             az_groups
-                .group_users_update(0, accounts.charlie, Role::Banned)
+                .invitations_create(0, accounts.charlie, Role::Admin, None)
                 .unwrap();
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            // ===== * it raises an error
-            result = az_groups.group_users_destroy(0, accounts.charlie);
-            assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // === when caller does not equal user
-            // ==== when caller role is less than 3 (less than admin)
-            // ==== * it raises an error
-            result = az_groups.group_users_destroy(0, accounts.bob);
+            az_groups.invitations_accept(0).unwrap();
+            // == * it raises an error
+            result = az_groups.invitations_destroy(0, accounts.django);
             assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // ==== when caller role is greater than or equal to 3
+            // == when caller's role is greater than or equal to the invited role
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            // == * it removes the invitation
+            az_groups.invitations_destroy(0, accounts.django).unwrap();
+            assert!(az_groups.invitations.get((0, accounts.django)).is_none());
+            // = when caller is the invitee
             az_groups
-                .group_users_update(0, accounts.charlie, Role::Admin)
+                .invitations_create(0, accounts.django, Role::Member, None)
                 .unwrap();
-            // ===== when caller's role is less than user's role
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            // = * it removes the invitation
+            az_groups.invitations_destroy(0, accounts.django).unwrap();
+            assert!(az_groups.invitations.get((0, accounts.django)).is_none());
+        }
+
+        #[ink::test]
+        fn test_role_permissions_update() {
+            let (accounts, mut az_groups) = init();
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // when caller is not a super admin
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            // ===== * it raises an error
-            result = az_groups.group_users_destroy(0, accounts.bob);
+            az_groups.group_users_create(0, None).unwrap();
+            // * it raises an error
+            let mut result = az_groups.role_permissions_update(
+                0,
+                Role::Member,
+                [Permission::ManageMembers].to_vec(),
+            );
             assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // ===== when caller's role is greater than or equal to user's role
+            // when caller is a super admin
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            az_groups
-                .group_users_update(0, accounts.charlie, Role::SuperAdmin)
-                .unwrap();
-            // ===== * it destroys UserGroup
-            az_groups.group_users_destroy(0, accounts.charlie).unwrap();
-            assert!(az_groups.group_users.get((0, accounts.charlie)).is_none());
+            // = when role is not a member or admin
+            // = * it raises an error
+            result = az_groups.role_permissions_update(
+                0,
+                Role::SuperAdmin,
+                [Permission::ManageMembers].to_vec(),
+            );
+            assert_eq!(
+                result,
+                Err(AZGroupsError::UnprocessableEntity(
+                    "Permissions can only be assigned to members and admins".to_string()
+                ))
+            );
+            // = when role is a member or admin
+            // = * it sets the permissions without duplicates
+            result = az_groups.role_permissions_update(
+                0,
+                Role::Member,
+                [Permission::ManageMembers, Permission::ManageMembers].to_vec(),
+            );
+            assert_eq!(result.unwrap(), [Permission::ManageMembers]);
+            // = * it applies to users with that role
+            az_groups.group_users_approve(0, accounts.charlie).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            az_groups.group_users_create(0, None).unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.group_users_approve(0, accounts.django).unwrap();
+            // = * it removes permissions that are not included
+            assert!(!az_groups.has_permission(0, accounts.charlie, Permission::ManageHubRecords));
         }
 
         #[ink::test]
-        fn test_group_users_update() {
+        fn test_super_admin_transfers_accept() {
             let (accounts, mut az_groups) = init();
-            let group_name: String = MOCK_GROUP_NAME.to_string();
-            // = when group with key exists
-            az_groups.groups_create(group_name).unwrap();
-            // == when caller equals user
-            // == * it raises an error
-            let mut result = az_groups.group_users_update(0, accounts.bob, Role::SuperAdmin);
-            assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // == when caller is different to user
-            // === when caller does not have a group user for team
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            result = az_groups.group_users_update(0, accounts.bob, Role::SuperAdmin);
-            // === * it raises an error
+            // when transfer does not exist
+            // * it raises an error
+            let mut result = az_groups.super_admin_transfers_accept(0);
             assert_eq!(
                 result,
-                Err(AZGroupsError::NotFound("GroupUser".to_string()))
+                Err(AZGroupsError::NotFound("SuperAdminTransfer".to_string()))
             );
-            // === when caller has a group user for team
+            // when transfer exists
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            // ==== when caller's role is less than 3
-            let mut caller_group_user: GroupUser =
-                az_groups.group_users.get((0, accounts.bob)).unwrap();
-            caller_group_user.role = Role::Member;
-            az_groups
-                .group_users
-                .insert((0, accounts.bob), &caller_group_user);
-            // ==== * it raises an error
-            result = az_groups.group_users_update(0, accounts.bob, Role::Member);
-            assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // ==== when caller's role is 3 or more
-            caller_group_user.role = Role::Admin;
             az_groups
-                .group_users
-                .insert((0, accounts.bob), &caller_group_user);
-            // ===== when user does not have a group user for team
-            result = az_groups.group_users_update(0, accounts.charlie, Role::SuperAdmin);
-            // ===== * it raises an error
-            assert_eq!(
-                result,
-                Err(AZGroupsError::NotFound("GroupUser".to_string()))
-            );
-            // ===== when user has a role with team
-            // ====== when caller's role is less than user's role
-            let mut user_group_user: GroupUser = GroupUser {
-                role: Role::SuperAdmin,
-            };
+                .super_admin_transfers_create(0, accounts.charlie)
+                .unwrap();
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            // = when sender is no longer a super admin
             az_groups
                 .group_users
-                .insert((0, accounts.charlie), &user_group_user);
-            // ====== * it raises an error
-            result = az_groups.group_users_update(0, accounts.charlie, Role::SuperAdmin);
+                .insert((0, accounts.bob), &GroupUser { role: Role::Admin });
+            // = * it raises an error
+            result = az_groups.super_admin_transfers_accept(0);
             assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // ====== when caller's role is greater than or equal to user's role
-            user_group_user = GroupUser { role: Role::Admin };
+            az_groups.group_users.insert(
+                (0, accounts.bob),
+                &GroupUser {
+                    role: Role::SuperAdmin,
+                },
+            );
+            // = when sender is a super admin
+            // == when recipient is banned
             az_groups
                 .group_users
-                .insert((0, accounts.charlie), &user_group_user);
-            // ======= when new role is less than or equal to caller's role
-            // ======= * it updates the user's role
-            result = az_groups.group_users_update(0, accounts.charlie, Role::Admin);
-            assert_eq!(result.unwrap().role, Role::Admin);
-            // ======= when new role is greater than caller's role
-            // ======= * it raises an error
-            result = az_groups.group_users_update(0, accounts.charlie, Role::SuperAdmin);
+                .insert((0, accounts.charlie), &GroupUser { role: Role::Banned });
+            // == * it raises an error
+            result = az_groups.super_admin_transfers_accept(0);
             assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            az_groups.group_users.remove((0, accounts.charlie));
+            // == when recipient is not banned
+            // == * it makes the recipient a super admin
+            result = az_groups.super_admin_transfers_accept(0);
+            assert_eq!(result.unwrap().role, Role::SuperAdmin);
+            assert_eq!(
+                az_groups
+                    .group_users_show(0, accounts.charlie)
+                    .unwrap()
+                    .role,
+                Role::SuperAdmin
+            );
+            // == * it makes the sender an admin
+            assert_eq!(
+                az_groups.group_users_show(0, accounts.bob).unwrap().role,
+                Role::Admin
+            );
+            assert_eq!(az_groups.super_admins_count(0), 1);
+            // == * it removes the transfer
+            assert!(az_groups
+                .super_admin_transfers
+                .get((0, accounts.charlie))
+                .is_none());
         }
 
         #[ink::test]
-        fn test_groups_create() {
+        fn test_super_admin_transfers_create() {
             let (accounts, mut az_groups) = init();
-            let group_name: String = MOCK_GROUP_NAME.to_string();
-            let key: String = group_name.to_lowercase();
-            // when group with key does not exist
-            // * it creates the group with the supplied name
-            // * it sets the group to enabled
-            // * it returns the created group
-            let mut result = az_groups.groups_create(group_name.clone());
-            let group = result.unwrap();
-            assert_eq!(group.name, group_name);
-            assert_eq!(group.enabled, true);
-            // * it creates and sets a new GroupUser with the caller as super admin
-            let group_user: GroupUser = az_groups.group_users.get((0, accounts.bob)).unwrap();
-            assert_eq!(group_user.role, Role::SuperAdmin);
-            // * it sets the group_id_by_name
-            assert_eq!(az_groups.group_id_by_name.get(key.clone()).unwrap(), 0);
-            // * it increases the groups total by one
-            assert_eq!(az_groups.groups_total, 1);
-            // when group with key already exists
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // when caller is not a super admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.group_users_create(0, None).unwrap();
             // * it raises an error
-            result = az_groups.groups_create(key);
+            let mut result = az_groups.super_admin_transfers_create(0, accounts.django);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // when caller is a super admin
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            // = when recipient is the caller
+            // = * it raises an error
+            result = az_groups.super_admin_transfers_create(0, accounts.bob);
             assert_eq!(
                 result,
                 Err(AZGroupsError::UnprocessableEntity(
-                    "Group has already been taken".to_string()
+                    "Recipient must be a different account".to_string()
                 ))
             );
-            // when groups_total is u32 max
-            az_groups.groups_total = u32::MAX;
-            // * it raises an error
-            result = az_groups.groups_create("XXXX".to_string());
+            // = when recipient is a different account
+            // = * it stores and returns the transfer
+            result = az_groups.super_admin_transfers_create(0, accounts.django);
+            let transfer: SuperAdminTransfer = result.unwrap();
             assert_eq!(
-                result,
-                Err(AZGroupsError::UnprocessableEntity(
-                    "Group limit reached".to_string()
-                ))
+                transfer,
+                SuperAdminTransfer {
+                    group_id: 0,
+                    from: accounts.bob,
+                    to: accounts.django,
+                }
             );
-            // when group_name is blank
-            // * it raises an error
-            result = az_groups.groups_create(" ".to_string());
             assert_eq!(
-                result,
-                Err(AZGroupsError::UnprocessableEntity(
-                    "Name can't be blank".to_string()
-                ))
+                az_groups
+                    .super_admin_transfers_show(0, accounts.django)
+                    .unwrap(),
+                transfer
             );
         }
 
         #[ink::test]
-        fn test_groups_find_by_name() {
-            let (_accounts, mut az_groups) = init();
-            let group_name: String = MOCK_GROUP_NAME.to_string();
-            // when group with name does not exist
+        fn test_super_admin_transfers_destroy() {
+            let (accounts, mut az_groups) = init();
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // when transfer does not exist
             // * it raises an error
-            let mut result = az_groups.groups_find_by_name(group_name.clone());
-            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
-            // when group with name exists
-            az_groups.groups_create(group_name.clone()).unwrap();
-            // = when name with no matching key is provided
+            let mut result = az_groups.super_admin_transfers_destroy(0, accounts.django);
+            assert_eq!(
+                result,
+                Err(AZGroupsError::NotFound("SuperAdminTransfer".to_string()))
+            );
+            // when transfer exists
+            az_groups
+                .super_admin_transfers_create(0, accounts.django)
+                .unwrap();
+            // = when caller is neither the sender nor the recipient
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
             // = * it raises an error
-            result = az_groups.groups_find_by_name("asdf".to_string());
-            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
-            // = when name with a matching key is provided (case insensitive)
-            // = * it returns the group
-            result = az_groups.groups_find_by_name(group_name.to_uppercase());
-            assert_eq!(result.unwrap(), az_groups.groups.get(0).unwrap());
+            result = az_groups.super_admin_transfers_destroy(0, accounts.django);
+            assert_eq!(result, Err(AZGroupsError::Unauthorised));
+            // = when caller is the recipient
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.django);
+            // = * it removes the transfer
+            az_groups
+                .super_admin_transfers_destroy(0, accounts.django)
+                .unwrap();
+            assert!(az_groups
+                .super_admin_transfers
+                .get((0, accounts.django))
+                .is_none());
+            // = when caller is the sender
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .super_admin_transfers_create(0, accounts.django)
+                .unwrap();
+            // = * it removes the transfer
+            az_groups
+                .super_admin_transfers_destroy(0, accounts.django)
+                .unwrap();
+            assert!(az_groups
+                .super_admin_transfers
+                .get((0, accounts.django))
+                .is_none());
         }
 
         #[ink::test]
-        fn test_groups_update() {
+        fn test_user_permissions_update() {
             let (accounts, mut az_groups) = init();
-            let group_name: String = MOCK_GROUP_NAME.to_string();
-            let key: String = group_name.to_lowercase();
-            // when group with key does not exist
-            // * it raises an error
-            let mut result = az_groups.groups_update(0, group_name.clone(), true);
-            assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
-            // when group with key exists
-            az_groups.groups_create(group_name.clone()).unwrap();
-            // = when caller is not part of group
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
-            // = * it raises an error
-            result = az_groups.groups_update(0, group_name.clone(), true);
+            az_groups.group_users_create(0, None).unwrap();
+            // when user does not have a group user
+            // * it raises an error
+            let mut result = az_groups.user_permissions_update(0, accounts.django, Vec::new());
             assert_eq!(
                 result,
                 Err(AZGroupsError::NotFound("GroupUser".to_string()))
             );
-            // = when caller is part of group
-            az_groups.group_users_create(0).unwrap();
-            // == when caller is not a super admin
-            // == * it raises an error
-            result = az_groups.groups_update(0, group_name.clone(), true);
+            // when user has a group user
+            // = when caller is not a super admin
+            // = * it raises an error
+            result = az_groups.user_permissions_update(
+                0,
+                accounts.charlie,
+                [Permission::EditGroup].to_vec(),
+            );
             assert_eq!(result, Err(AZGroupsError::Unauthorised));
-            // == when caller is a super admin
+            // = when caller is a super admin
             ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
-            // === when new_name is present
-            // ==== when new_name is empty blank
-            // ==== * it raises an error
-            result = az_groups.groups_update(0, " ".to_string(), false);
+            // == when user is an applicant
+            // == * it raises an error
+            result = az_groups.user_permissions_update(
+                0,
+                accounts.charlie,
+                [Permission::EditGroup].to_vec(),
+            );
             assert_eq!(
                 result,
                 Err(AZGroupsError::UnprocessableEntity(
-                    "Name can't be blank".to_string()
+                    "Permissions can only be assigned to members and admins".to_string()
                 ))
             );
-            // ==== when new_name is available
-            // ==== * it updates the group
-            let mut new_name: String = "King Kong".to_string();
-            result = az_groups.groups_update(0, new_name.clone(), false);
-            assert_eq!(
-                result.unwrap(),
-                Group {
-                    id: 0,
-                    name: new_name.clone(),
-                    enabled: false
-                }
-            );
-            // ==== * it removes the old group_id_by_name map
-            assert!(az_groups.group_id_by_name.get(key).is_none());
-            // ==== * it create the new group_id_by_name map
-            assert_eq!(
-                az_groups
-                    .group_id_by_name
-                    .get(new_name.to_lowercase())
-                    .unwrap(),
-                0
-            );
-            // ==== when new_name is taken
-            // ===== when new_name's key is the same as the original key
-            new_name = new_name.to_uppercase() + " ";
-            result = az_groups.groups_update(0, new_name.clone(), true);
-            // ===== * it updates
-            assert_eq!(
-                result.unwrap(),
-                Group {
-                    id: 0,
-                    name: AZGroups::format_group_name(new_name),
-                    enabled: true
-                }
+            // == when user is a member
+            az_groups.group_users_approve(0, accounts.charlie).unwrap();
+            // == * it grants the permissions to the user
+            result = az_groups.user_permissions_update(
+                0,
+                accounts.charlie,
+                [Permission::EditGroup].to_vec(),
             );
-            // ===== when new_name's key is different from the original key
-            az_groups.group_id_by_name.insert("a".to_string(), &1);
-            result = az_groups.groups_update(0, "A".to_string(), true);
-            // ===== * it raises an error
+            assert_eq!(result.unwrap(), [Permission::EditGroup]);
             assert_eq!(
-                result,
-                Err(AZGroupsError::UnprocessableEntity(
-                    "Group has already been taken".to_string()
-                ))
+                az_groups.user_permissions(0, accounts.charlie),
+                [Permission::EditGroup]
             );
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.groups_update_application_cooldown(0, 10).unwrap();
+            // == when user is banned
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups
+                .group_users_update(0, accounts.charlie, Role::Banned)
+                .unwrap();
+            // == * it removes the granted permissions
+            assert!(az_groups.user_permissions(0, accounts.charlie).is_empty());
+        }
+
+        #[ink::test]
+        fn test_has_permission() {
+            let (accounts, mut az_groups) = init();
+            // when group user does not exist
+            // * it returns false
+            assert!(!az_groups.has_permission(0, accounts.bob, Permission::EditGroup));
+            // when group user is a super admin
+            az_groups
+                .groups_create(MOCK_GROUP_NAME.to_string(), None, None, None, None)
+                .unwrap();
+            // * it returns true
+            assert!(az_groups.has_permission(0, accounts.bob, Permission::EditGroup));
+            // when group user is an applicant
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.charlie);
+            az_groups.group_users_create(0, None).unwrap();
+            // * it returns false
+            assert!(!az_groups.has_permission(0, accounts.charlie, Permission::ManageHubRecords));
+            // when group user is a member
+            ink::env::test::set_caller::<ink::env::DefaultEnvironment>(accounts.bob);
+            az_groups.group_users_approve(0, accounts.charlie).unwrap();
+            // = when permission is in the role's permissions
+            // = * it returns true
+            assert!(az_groups.has_permission(0, accounts.charlie, Permission::ManageHubRecords));
+            // = when permission is not in the role's permissions
+            // == when permission has not been granted to the user
+            // == * it returns false
+            assert!(!az_groups.has_permission(0, accounts.charlie, Permission::ManageMembers));
+            // == when permission has been granted to the user
+            az_groups
+                .user_permissions_update(0, accounts.charlie, [Permission::ManageMembers].to_vec())
+                .unwrap();
+            // == * it returns true
+            assert!(az_groups.has_permission(0, accounts.charlie, Permission::ManageMembers));
         }
 
         #[ink::test]
@@ -736,7 +2981,9 @@ mod az_groups {
             let mut result = az_groups.validate_membership(0, accounts.bob);
             assert_eq!(result, Err(AZGroupsError::NotFound("Group".to_string())));
             // when group with id exists
-            let mut group: Group = az_groups.groups_create(group_name).unwrap();
+            let mut group: Group = az_groups
+                .groups_create(group_name, None, None, None, None)
+                .unwrap();
             // = when group is enabled
             // == when GroupUser doesn't exist
             result = az_groups.validate_membership(0, accounts.alice);
@@ -0,0 +1,125 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+// Types shared by AZ Groups and the smart contracts that call it.
+// Variants are SCALE encoded by index, so they must only ever be appended.
+mod errors;
+
+pub use errors::AZGroupsError;
+
+use ink::primitives::AccountId;
+
+#[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Role {
+    Banned,
+    Applicant,
+    Member,
+    Admin,
+    SuperAdmin,
+}
+impl Role {
+    pub fn to_int(&self) -> u8 {
+        match *self {
+            Role::Banned => 0,
+            Role::Applicant => 1,
+            Role::Member => 2,
+            Role::Admin => 3,
+            Role::SuperAdmin => 4,
+        }
+    }
+}
+
+// Actions within a group that can be granted to a role or to an individual group user.
+#[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum Permission {
+    // Approve, reject, update and remove group users
+    ManageMembers,
+    // Create and revoke invitations
+    ManageInvitations,
+    // Update the group's details and settings
+    EditGroup,
+    // Act on smart contract hub records linked to the group
+    ManageHubRecords,
+}
+
+// The membership API that AZ Groups exposes to other smart contracts.
+// The selector is pinned to that of the original validate_membership message.
+#[ink::trait_definition]
+pub trait AZGroupsMembership {
+    // Convenience method so that other contract can get this info without having to call two functions
+    // 1. Check that the group is enabled
+    // 2. Check that user has a role with the group greater than or equal to two
+    #[ink(message, selector = 0xc5160381)]
+    fn validate_membership(&self, group_id: u32, user: AccountId) -> Result<Role, AZGroupsError>;
+
+    // Whether the user has the permission in the group, through their role or a grant to them.
+    #[ink(message)]
+    fn has_permission(&self, group_id: u32, user: AccountId, permission: Permission) -> bool;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink::LangError;
+    use scale::{Decode, Encode};
+
+    fn assert_encoding<T: Encode + Decode + PartialEq + core::fmt::Debug>(value: T, bytes: &[u8]) {
+        assert_eq!(value.encode(), bytes);
+        assert_eq!(T::decode(&mut &*bytes).unwrap(), value);
+    }
+
+    #[test]
+    fn test_role_encoding() {
+        for (role, index) in [
+            (Role::Banned, 0),
+            (Role::Applicant, 1),
+            (Role::Member, 2),
+            (Role::Admin, 3),
+            (Role::SuperAdmin, 4),
+        ] {
+            // * it encodes to the same index as its int
+            assert_eq!(role.to_int(), index);
+            assert_encoding(role, &[index]);
+        }
+    }
+
+    #[test]
+    fn test_permission_encoding() {
+        assert_encoding(Permission::ManageMembers, &[0]);
+        assert_encoding(Permission::ManageInvitations, &[1]);
+        assert_encoding(Permission::EditGroup, &[2]);
+        assert_encoding(Permission::ManageHubRecords, &[3]);
+    }
+
+    #[test]
+    fn test_az_groups_error_encoding() {
+        // "Group" is encoded as a compact length prefix followed by its bytes
+        let group: &[u8] = &[20, b'G', b'r', b'o', b'u', b'p'];
+        assert_encoding(
+            AZGroupsError::ContractCall(LangError::CouldNotReadInput),
+            &[0, 1],
+        );
+        assert_encoding(AZGroupsError::GroupDisabled, &[1]);
+        assert_encoding(
+            AZGroupsError::InkEnvError("Group".to_string()),
+            &[&[2], group].concat(),
+        );
+        assert_encoding(AZGroupsError::NotAMember, &[3]);
+        assert_encoding(
+            AZGroupsError::NotFound("Group".to_string()),
+            &[&[4], group].concat(),
+        );
+        assert_encoding(AZGroupsError::Unauthorised, &[5]);
+        assert_encoding(
+            AZGroupsError::UnprocessableEntity("Group".to_string()),
+            &[&[6], group].concat(),
+        );
+    }
+}
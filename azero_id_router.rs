@@ -0,0 +1,51 @@
+// In-memory stand-in for the AZERO.ID router, used by unit tests as cross-contract calls
+// aren't supported off-chain. Each test runs on its own thread so nothing leaks between tests.
+#[cfg(test)]
+pub mod in_memory {
+    use ink::primitives::AccountId;
+    use std::{cell::RefCell, collections::BTreeMap};
+
+    pub const DOMAIN_NOT_FOUND: u8 = 0;
+
+    thread_local! {
+        static ADDRESSES: RefCell<BTreeMap<String, AccountId>> = const { RefCell::new(BTreeMap::new()) };
+        static PRIMARY_DOMAINS: RefCell<BTreeMap<AccountId, String>> = const { RefCell::new(BTreeMap::new()) };
+    }
+
+    pub fn get_address(domain: String) -> Result<AccountId, u8> {
+        ADDRESSES.with(|addresses| {
+            addresses
+                .borrow()
+                .get(&domain)
+                .copied()
+                .ok_or(DOMAIN_NOT_FOUND)
+        })
+    }
+
+    pub fn get_primary_domains(account: AccountId) -> Vec<(AccountId, String)> {
+        PRIMARY_DOMAINS.with(|primary_domains| {
+            primary_domains
+                .borrow()
+                .get(&account)
+                .map(|domain| vec![(AccountId::from([0; 32]), domain.clone())])
+                .unwrap_or_default()
+        })
+    }
+
+    pub fn set_address(domain: &str, address: Option<AccountId>) {
+        ADDRESSES.with(|addresses| match address {
+            Some(address_unwrapped) => addresses
+                .borrow_mut()
+                .insert(domain.to_string(), address_unwrapped),
+            None => addresses.borrow_mut().remove(domain),
+        });
+    }
+
+    pub fn set_primary_domain(account: AccountId, domain: &str) {
+        PRIMARY_DOMAINS.with(|primary_domains| {
+            primary_domains
+                .borrow_mut()
+                .insert(account, domain.to_string())
+        });
+    }
+}
@@ -1,3 +1,4 @@
+use az_groups_types::AZGroupsError;
 use ink::{
     env::Error as InkEnvError,
     prelude::{format, string::String},
@@ -12,6 +13,8 @@ pub enum AZSmartContractHubError {
     Unauthorised,
     UnprocessableEntity(String),
     AZGroupsError(AZGroupsError),
+    // The error code returned by the AZERO.ID router.
+    AzeroIdRouterError(u8),
 }
 impl From<AZGroupsError> for AZSmartContractHubError {
     fn from(error: AZGroupsError) -> Self {
@@ -28,15 +31,3 @@ impl From<LangError> for AZSmartContractHubError {
         AZSmartContractHubError::ContractCall(e)
     }
 }
-
-#[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
-#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
-pub enum AZGroupsError {
-    ContractCall(LangError),
-    GroupDisabled,
-    InkEnvError(String),
-    NotAMember,
-    NotFound(String),
-    Unauthorised,
-    UnprocessableEntity(String),
-}
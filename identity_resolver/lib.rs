@@ -0,0 +1,23 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use ink::{
+    prelude::{string::String, vec::Vec},
+    primitives::AccountId,
+};
+
+// Resolves identities (e.g. AZERO.IDs) to accounts and back.
+// The selectors match the AZERO.ID router's so that the router can be used directly.
+#[ink::trait_definition]
+pub trait IdentityResolver {
+    // Returns the address a domain resolves to, or the resolver's error code.
+    #[ink(message, selector = 0xd259f7ba)]
+    fn get_address(&self, domain: String) -> Result<AccountId, u8>;
+
+    // Returns the (registry, domain) pairs set as primary for an account.
+    #[ink(message, selector = 0xdf3a358e)]
+    fn get_primary_domains(
+        &self,
+        account: AccountId,
+        tld: Option<String>,
+    ) -> Vec<(AccountId, String)>;
+}
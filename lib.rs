@@ -1,39 +1,120 @@
 #![cfg_attr(not(feature = "std"), no_std, no_main)]
 
+mod azero_id_router;
 mod errors;
+mod membership_checker;
 
 #[ink::contract]
 mod az_smart_contract_hub {
-    use crate::errors::{AZGroupsError, AZSmartContractHubError};
+    #[cfg(test)]
+    use crate::membership_checker::in_memory::InMemoryMembershipChecker;
+    #[cfg(not(test))]
+    use crate::membership_checker::AZGroupsMembershipChecker;
+    use crate::{errors::AZSmartContractHubError, membership_checker::MembershipChecker};
+    use az_groups_types::Permission;
+    #[cfg(not(test))]
+    use identity_resolver::IdentityResolver;
     use ink::{
         codegen::EmitEvent,
         env::call::{build_call, ExecutionInput, Selector},
         prelude::{
             format,
             string::{String, ToString},
+            vec::Vec,
         },
         reflect::ContractEventBase,
-        storage::Mapping,
+        storage::{traits::StorageKey, Mapping},
     };
+    #[cfg(not(test))]
+    use ink::{codegen::TraitCallBuilder, contract_ref};
 
-    const MOCK_VALID_AZERO_ID: &str = "MOCK VALID AZERO ID";
-    const MOCK_INVALID_AZERO_ID: &str = "MOCK INVALID AZERO ID";
+    const FLAG_TEXT_LENGTH_LIMIT: usize = 280;
+    const INTERFACES_LIMIT: usize = 10;
+    const TAGS_LIMIT: usize = 5;
+    const TAG_LENGTH_LIMIT: usize = 32;
 
     // === TYPES ===
     type Event = <AZSmartContractHub as ContractEventBase>::Type;
     type Result<T> = core::result::Result<T, AZSmartContractHubError>;
+    // (smart_contract_id, flag_round, user)
+    type FlagKey = (u32, u32, AccountId);
+    // (chain, smart_contract_address)
+    type SmartContractAddressKey = (u8, AccountId);
 
     // === ENUMS ===
     #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
-    pub enum Role {
-        Banned,
-        Applicant,
-        Member,
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum FlagReason {
+        MaliciousAbi,
+        Phishing,
+        Impersonation,
+        Spam,
+        Other,
+    }
+
+    // Admins can do everything that moderators and fee managers can.
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, Copy, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum HubRole {
         Admin,
-        SuperAdmin,
+        Moderator,
+        FeeManager,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub enum Verdict {
+        Passed,
+        PassedWithIssues,
+        Failed,
     }
 
     // === EVENTS ===
+    #[ink(event)]
+    pub struct AttestationCreate {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        smart_contract_id: u32,
+        #[ink(topic)]
+        auditor: AccountId,
+        report_url: String,
+        report_hash: Hash,
+        scope_code_hash: Hash,
+        verdict: Verdict,
+        timestamp: Timestamp,
+    }
+
+    #[ink(event)]
+    pub struct AuditorCreate {
+        #[ink(topic)]
+        account: AccountId,
+        name: String,
+    }
+
+    #[ink(event)]
+    pub struct AuditorUpdate {
+        #[ink(topic)]
+        account: AccountId,
+        name: String,
+        enabled: bool,
+    }
+
+    #[ink(event)]
+    pub struct ClearFlags {
+        #[ink(topic)]
+        id: u32,
+    }
+
     #[ink(event)]
     pub struct Create {
         #[ink(topic)]
@@ -52,6 +133,82 @@ mod az_smart_contract_hub {
         project_name: Option<String>,
         project_website: Option<String>,
         github: Option<String>,
+        created_at: Timestamp,
+        created_at_block: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct Destroy {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        caller: AccountId,
+        deposit: Balance,
+    }
+
+    #[ink(event)]
+    pub struct Endorse {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        user: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct FlagCreate {
+        #[ink(topic)]
+        smart_contract_id: u32,
+        #[ink(topic)]
+        user: AccountId,
+        reason: FlagReason,
+        text: Option<String>,
+    }
+
+    #[ink(event)]
+    pub struct InterfaceCreate {
+        #[ink(topic)]
+        id: u32,
+        name: String,
+        selector: Option<[u8; 4]>,
+    }
+
+    #[ink(event)]
+    pub struct InterfaceUpdate {
+        #[ink(topic)]
+        id: u32,
+        name: String,
+        selector: Option<[u8; 4]>,
+        enabled: bool,
+    }
+
+    #[ink(event)]
+    pub struct Revalidate {
+        #[ink(topic)]
+        id: u32,
+        azero_id: String,
+        stale: bool,
+    }
+
+    #[ink(event)]
+    pub struct RoleGrant {
+        role: HubRole,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct RoleRevoke {
+        role: HubRole,
+        #[ink(topic)]
+        account: AccountId,
+    }
+
+    #[ink(event)]
+    pub struct Unendorse {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        user: AccountId,
     }
 
     #[ink(event)]
@@ -65,17 +222,143 @@ mod az_smart_contract_hub {
         project_name: Option<String>,
         project_website: Option<String>,
         github: Option<String>,
+        updated_at: Timestamp,
+        updated_at_block: BlockNumber,
+    }
+
+    #[ink(event)]
+    pub struct UpdateCanonical {
+        chain: u8,
+        #[ink(topic)]
+        smart_contract_address: AccountId,
+        id: Option<u32>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateHidden {
+        #[ink(topic)]
+        id: u32,
+        hidden: bool,
+    }
+
+    #[ink(event)]
+    pub struct UpdateInterfaces {
+        #[ink(topic)]
+        id: u32,
+        interface_ids: Vec<u32>,
+    }
+
+    #[ink(event)]
+    pub struct UpdateTags {
+        #[ink(topic)]
+        id: u32,
+        tags: Vec<String>,
+    }
+
+    #[ink(event)]
+    pub struct VerifyInterface {
+        #[ink(topic)]
+        id: u32,
+        #[ink(topic)]
+        interface_id: u32,
     }
 
     // === STRUCTS ===
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Attestation {
+        id: u32,
+        smart_contract_id: u32,
+        auditor: AccountId,
+        report_url: String,
+        report_hash: Hash,
+        scope_code_hash: Hash,
+        verdict: Verdict,
+        timestamp: Timestamp,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Auditor {
+        account: AccountId,
+        name: String,
+        enabled: bool,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct AzeroIdStatus {
+        azero_id: String,
+        // Whether the AZERO.ID no longer resolves to the caller of the smart contract record.
+        stale: bool,
+        checked_at: Timestamp,
+        checked_at_block: BlockNumber,
+    }
+
     #[derive(Debug, Clone, scale::Encode, scale::Decode)]
     #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
     pub struct Config {
         admin: AccountId,
+        attestations_count: u32,
         az_groups_address: AccountId,
         azero_id_router_address: AccountId,
+        chain: u8,
+        endorsements_require_azero_id: bool,
         fee: Balance,
+        import_open: bool,
+        interfaces_count: u32,
         smart_contracts_count: u32,
+        storage_deposit: Balance,
+        storage_deposit_per_byte: Balance,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Flag {
+        reason: FlagReason,
+        text: Option<String>,
+    }
+
+    // A standard such as PSP22 that smart contracts can declare that they implement.
+    // The selector should belong to a message that takes no arguments and doesn't change state,
+    // as it is called on the smart contract to verify the declaration.
+    // Only whether the message is dispatched is checked, not what it returns.
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    pub struct Interface {
+        id: u32,
+        name: String,
+        selector: Option<[u8; 4]>,
+        // Increased whenever the selector changes, so that earlier verifications no longer apply.
+        selector_version: u32,
+        enabled: bool,
+    }
+
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(
+        feature = "std",
+        derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+    )]
+    // A declaration is only verified for the selector version it was verified against.
+    pub struct InterfaceDeclaration {
+        interface_id: u32,
+        verified: bool,
+        selector_version: u32,
     }
 
     #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
@@ -98,61 +381,540 @@ mod az_smart_contract_hub {
         project_name: Option<String>,
         project_website: Option<String>,
         github: Option<String>,
+        tags: Vec<String>,
+        interfaces: Vec<InterfaceDeclaration>,
+        endorsements_count: u32,
+        flags_count: u32,
+        // Set by the admin, separate from enabled which is set by the caller.
+        hidden: bool,
+        created_at: Timestamp,
+        created_at_block: BlockNumber,
+        updated_at: Timestamp,
+        updated_at_block: BlockNumber,
+    }
+
+    // The smart contract record as stored by the original deployment.
+    // Used to import existing records into a new deployment.
+    #[derive(scale::Decode, scale::Encode, Debug, Clone, PartialEq)]
+    #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+    pub struct SmartContractV0 {
+        id: u32,
+        smart_contract_address: AccountId,
+        chain: u8,
+        caller: AccountId,
+        enabled: bool,
+        azero_id: String,
+        abi_url: String,
+        contract_url: Option<String>,
+        wasm_url: Option<String>,
+        audit_url: Option<String>,
+        group_id: Option<u32>,
+        project_name: Option<String>,
+        project_website: Option<String>,
+        github: Option<String>,
     }
 
     // === CONTRACT ===
     #[ink(storage)]
     pub struct AZSmartContractHub {
         admin: AccountId,
+        // (auditor, position) => attestation_id
+        attestation_ids_by_auditor: Mapping<(AccountId, u32), u32>,
+        // (smart_contract_id, position) => attestation_id
+        attestation_ids_by_smart_contract: Mapping<(u32, u32), u32>,
+        // (auditor, attestation_id) => position
+        attestation_positions_by_auditor: Mapping<(AccountId, u32), u32>,
+        // (smart_contract_id, attestation_id) => position
+        attestation_positions_by_smart_contract: Mapping<(u32, u32), u32>,
+        attestations: Mapping<u32, Attestation>,
+        attestations_count: u32,
+        attestations_count_by_auditor: Mapping<AccountId, u32>,
+        attestations_count_by_smart_contract: Mapping<u32, u32>,
+        auditors: Mapping<AccountId, Auditor>,
         az_groups_address: AccountId,
         azero_id_router_address: AccountId,
+        azero_id_statuses: Mapping<u32, AzeroIdStatus>,
+        canonical_ids: Mapping<SmartContractAddressKey, u32>,
+        chain: u8,
+        deposits: Mapping<u32, Balance>,
+        endorsements: Mapping<(u32, AccountId), ()>,
+        endorsements_require_azero_id: bool,
         fee: Balance,
+        // Increased when a smart contract's flags are cleared, so that earlier flags no longer apply.
+        flag_rounds: Mapping<u32, u32>,
+        flags: Mapping<FlagKey, Flag>,
+        // Records can only be imported, and not created, until the admin finalises the import.
+        import_open: bool,
+        interfaces: Mapping<u32, Interface>,
+        interfaces_count: u32,
+        roles: Mapping<(HubRole, AccountId), ()>,
+        // (smart_contract_address_key, position) => smart_contract_id
+        smart_contract_ids_by_address: Mapping<(SmartContractAddressKey, u32), u32>,
+        // (interface_id, position) => smart_contract_id
+        smart_contract_ids_by_interface: Mapping<(u32, u32), u32>,
+        // (tag, position) => smart_contract_id
+        smart_contract_ids_by_tag: Mapping<(String, u32), u32>,
+        // (smart_contract_address_key, smart_contract_id) => position
+        smart_contract_positions_by_address: Mapping<(SmartContractAddressKey, u32), u32>,
+        // (interface_id, smart_contract_id) => position
+        smart_contract_positions_by_interface: Mapping<(u32, u32), u32>,
+        // (tag, smart_contract_id) => position
+        smart_contract_positions_by_tag: Mapping<(String, u32), u32>,
         smart_contracts: Mapping<u32, SmartContract>,
         smart_contracts_count: u32,
+        smart_contracts_count_by_address: Mapping<SmartContractAddressKey, u32>,
+        smart_contracts_count_by_interface: Mapping<u32, u32>,
+        smart_contracts_count_by_tag: Mapping<String, u32>,
+        storage_deposit: Balance,
+        storage_deposit_per_byte: Balance,
     }
     impl AZSmartContractHub {
         #[ink(constructor)]
-        pub fn new(azero_id_router_address: AccountId, az_groups_address: AccountId) -> Self {
+        pub fn new(
+            azero_id_router_address: AccountId,
+            az_groups_address: AccountId,
+            chain: u8,
+        ) -> Self {
+            let mut roles = Mapping::default();
+            roles.insert((HubRole::Admin, Self::env().caller()), &());
             Self {
                 admin: Self::env().caller(),
+                attestation_ids_by_auditor: Mapping::default(),
+                attestation_ids_by_smart_contract: Mapping::default(),
+                attestation_positions_by_auditor: Mapping::default(),
+                attestation_positions_by_smart_contract: Mapping::default(),
+                attestations: Mapping::default(),
+                attestations_count: 0,
+                attestations_count_by_auditor: Mapping::default(),
+                attestations_count_by_smart_contract: Mapping::default(),
+                auditors: Mapping::default(),
                 az_groups_address,
                 azero_id_router_address,
+                azero_id_statuses: Mapping::default(),
+                canonical_ids: Mapping::default(),
+                chain,
+                deposits: Mapping::default(),
+                endorsements: Mapping::default(),
+                endorsements_require_azero_id: false,
                 fee: 1_000,
+                flag_rounds: Mapping::default(),
+                flags: Mapping::default(),
+                import_open: true,
+                interfaces: Mapping::default(),
+                interfaces_count: 0,
+                roles,
+                smart_contract_ids_by_address: Mapping::default(),
+                smart_contract_positions_by_address: Mapping::default(),
+                smart_contract_ids_by_interface: Mapping::default(),
+                smart_contract_positions_by_interface: Mapping::default(),
+                smart_contract_ids_by_tag: Mapping::default(),
+                smart_contract_positions_by_tag: Mapping::default(),
                 smart_contracts: Mapping::default(),
                 smart_contracts_count: 0,
+                smart_contracts_count_by_address: Mapping::default(),
+                smart_contracts_count_by_interface: Mapping::default(),
+                smart_contracts_count_by_tag: Mapping::default(),
+                storage_deposit: 0,
+                storage_deposit_per_byte: 0,
             }
         }
 
         // === QUERIES ===
+        #[ink(message)]
+        pub fn attestations_by_auditor(
+            &self,
+            auditor: AccountId,
+            page: u32,
+            size: u8,
+        ) -> Vec<Attestation> {
+            Self::paged_ids(
+                &self.attestation_ids_by_auditor,
+                &self.attestations_count_by_auditor,
+                auditor,
+                page,
+                size,
+            )
+            .filter_map(|id| self.attestations.get(id))
+            .collect()
+        }
+
+        #[ink(message)]
+        pub fn attestations_by_smart_contract(
+            &self,
+            smart_contract_id: u32,
+            page: u32,
+            size: u8,
+        ) -> Vec<Attestation> {
+            Self::paged_ids(
+                &self.attestation_ids_by_smart_contract,
+                &self.attestations_count_by_smart_contract,
+                smart_contract_id,
+                page,
+                size,
+            )
+            .filter_map(|id| self.attestations.get(id))
+            .collect()
+        }
+
+        #[ink(message)]
+        pub fn attestations_show(&self, id: u32) -> Result<Attestation> {
+            self.attestations
+                .get(id)
+                .ok_or(AZSmartContractHubError::NotFound("Attestation".to_string()))
+        }
+
+        #[ink(message)]
+        pub fn auditors_show(&self, account: AccountId) -> Result<Auditor> {
+            self.auditors
+                .get(account)
+                .ok_or(AZSmartContractHubError::NotFound("Auditor".to_string()))
+        }
+
+        // Until a smart contract is revalidated, its status is from when the AZERO.ID was last validated.
+        #[ink(message)]
+        pub fn azero_id_status(&self, id: u32) -> Result<AzeroIdStatus> {
+            let smart_contract: SmartContract = self.show(id)?;
+
+            Ok(self.azero_id_statuses.get(id).unwrap_or(AzeroIdStatus {
+                azero_id: smart_contract.azero_id,
+                stale: false,
+                checked_at: smart_contract.updated_at,
+                checked_at_block: smart_contract.updated_at_block,
+            }))
+        }
+
+        // A hidden or disabled canonical smart contract is treated as not found.
+        #[ink(message)]
+        pub fn canonical_for(
+            &self,
+            chain: u8,
+            smart_contract_address: AccountId,
+        ) -> Result<SmartContract> {
+            self.canonical_ids
+                .get((chain, smart_contract_address))
+                .and_then(|id| self.smart_contract(id))
+                .filter(|smart_contract| !smart_contract.hidden && smart_contract.enabled)
+                .ok_or(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string(),
+                ))
+        }
+
         #[ink(message)]
         pub fn config(&self) -> Config {
             Config {
                 admin: self.admin,
+                attestations_count: self.attestations_count,
                 az_groups_address: self.az_groups_address,
                 azero_id_router_address: self.azero_id_router_address,
+                chain: self.chain,
+                endorsements_require_azero_id: self.endorsements_require_azero_id,
                 fee: self.fee,
+                import_open: self.import_open,
+                interfaces_count: self.interfaces_count,
                 smart_contracts_count: self.smart_contracts_count,
+                storage_deposit: self.storage_deposit,
+                storage_deposit_per_byte: self.storage_deposit_per_byte,
             }
         }
 
         #[ink(message)]
-        pub fn show(&self, id: u32) -> Result<SmartContract> {
-            self.smart_contracts
+        pub fn deposit_of(&self, id: u32) -> Result<Balance> {
+            self.show(id)?;
+
+            Ok(self.deposits.get(id).unwrap_or(0))
+        }
+
+        #[ink(message)]
+        pub fn flags_show(&self, smart_contract_id: u32, user: AccountId) -> Result<Flag> {
+            self.show(smart_contract_id)?;
+
+            self.flags
+                .get(self.flag_key(smart_contract_id, user))
+                .ok_or(AZSmartContractHubError::NotFound("Flag".to_string()))
+        }
+
+        #[ink(message)]
+        pub fn has_role(&self, role: HubRole, account: AccountId) -> bool {
+            self.roles.contains((role, account))
+        }
+
+        #[ink(message)]
+        pub fn interfaces_index(&self) -> Vec<Interface> {
+            (0..self.interfaces_count)
+                .filter_map(|id| self.interfaces.get(id))
+                .collect()
+        }
+
+        #[ink(message)]
+        pub fn interfaces_show(&self, id: u32) -> Result<Interface> {
+            self.interfaces
                 .get(id)
+                .ok_or(AZSmartContractHubError::NotFound("Interface".to_string()))
+        }
+
+        // Hidden and disabled smart contracts are ignored.
+        // When smart contracts have the same number of endorsements, the oldest is returned.
+        #[ink(message)]
+        pub fn most_endorsed(
+            &self,
+            chain: u8,
+            smart_contract_address: AccountId,
+        ) -> Result<SmartContract> {
+            let mut most_endorsed: Option<SmartContract> = None;
+            for id in Self::indexed_ids(
+                &self.smart_contract_ids_by_address,
+                &self.smart_contracts_count_by_address,
+                (chain, smart_contract_address),
+            ) {
+                if let Some(smart_contract) = self.smart_contract(id) {
+                    if smart_contract.hidden || !smart_contract.enabled {
+                        continue;
+                    }
+                    if most_endorsed.as_ref().is_none_or(|most_endorsed| {
+                        smart_contract.endorsements_count > most_endorsed.endorsements_count
+                    }) {
+                        most_endorsed = Some(smart_contract)
+                    }
+                }
+            }
+
+            most_endorsed.ok_or(AZSmartContractHubError::NotFound(
+                "SmartContract".to_string(),
+            ))
+        }
+
+        // Hidden smart contracts are still returned, so that they can be looked up by id.
+        #[ink(message)]
+        pub fn show(&self, id: u32) -> Result<SmartContract> {
+            self.smart_contract(id)
                 .ok_or(AZSmartContractHubError::NotFound(
                     "SmartContract".to_string(),
                 ))
         }
 
-        // === HANDLES ===
-        #[allow(clippy::too_many_arguments)]
-        #[ink(message, payable)]
-        pub fn create(
-            &mut self,
-            smart_contract_address: AccountId,
-            chain: u8,
-            azero_id: String,
-            abi_url: String,
+        // Smart contracts are returned in the order they declared the interface,
+        // except that removing a smart contract moves the last one into its position.
+        #[ink(message)]
+        pub fn smart_contracts_by_interface(
+            &self,
+            interface_id: u32,
+            page: u32,
+            size: u8,
+        ) -> Vec<SmartContract> {
+            let ids = Self::indexed_ids(
+                &self.smart_contract_ids_by_interface,
+                &self.smart_contracts_count_by_interface,
+                interface_id,
+            );
+            self.smart_contracts_by_ids(ids, page, size)
+        }
+
+        // Smart contracts are returned in the order they were tagged,
+        // except that removing a smart contract moves the last one into its position.
+        #[ink(message)]
+        pub fn smart_contracts_by_tag(
+            &self,
+            tag: String,
+            page: u32,
+            size: u8,
+        ) -> Vec<SmartContract> {
+            let ids = Self::indexed_ids(
+                &self.smart_contract_ids_by_tag,
+                &self.smart_contracts_count_by_tag,
+                Self::format_tag(tag),
+            );
+            self.smart_contracts_by_ids(ids, page, size)
+        }
+
+        // === HANDLES ===
+        // Only enabled auditors can attest.
+        #[ink(message)]
+        pub fn attestations_create(
+            &mut self,
+            smart_contract_id: u32,
+            report_url: String,
+            report_hash: Hash,
+            scope_code_hash: Hash,
+            verdict: Verdict,
+        ) -> Result<Attestation> {
+            let caller: AccountId = Self::env().caller();
+            if !self.auditors_show(caller)?.enabled {
+                return Err(AZSmartContractHubError::Unauthorised);
+            }
+            self.show(smart_contract_id)?;
+            if self.attestations_count == u32::MAX {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Attestation limit reached".to_string(),
+                ));
+            }
+            let report_url_formatted: String = self.format_url(report_url);
+            Self::validate_presence_of(&report_url_formatted, "Link to report")?;
+
+            let attestation: Attestation = Attestation {
+                id: self.attestations_count,
+                smart_contract_id,
+                auditor: caller,
+                report_url: report_url_formatted,
+                report_hash,
+                scope_code_hash,
+                verdict,
+                timestamp: self.env().block_timestamp(),
+            };
+            self.attestations.insert(attestation.id, &attestation);
+            self.attestations_count = self.attestations_count.checked_add(1).unwrap();
+            Self::add_to_paged_index(
+                &mut self.attestation_ids_by_smart_contract,
+                &mut self.attestation_positions_by_smart_contract,
+                &mut self.attestations_count_by_smart_contract,
+                &smart_contract_id,
+                attestation.id,
+            );
+            Self::add_to_paged_index(
+                &mut self.attestation_ids_by_auditor,
+                &mut self.attestation_positions_by_auditor,
+                &mut self.attestations_count_by_auditor,
+                &caller,
+                attestation.id,
+            );
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::AttestationCreate(AttestationCreate {
+                    id: attestation.id,
+                    smart_contract_id,
+                    auditor: caller,
+                    report_url: attestation.report_url.clone(),
+                    report_hash,
+                    scope_code_hash,
+                    verdict: attestation.verdict.clone(),
+                    timestamp: attestation.timestamp,
+                }),
+            );
+
+            Ok(attestation)
+        }
+
+        #[ink(message)]
+        pub fn auditors_create(&mut self, account: AccountId, name: String) -> Result<Auditor> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+            if self.auditors.contains(account) {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Auditor has already been taken".to_string(),
+                ));
+            }
+            let name_formatted: String = name.trim().to_string();
+            Self::validate_presence_of(&name_formatted, "Name")?;
+
+            let auditor: Auditor = Auditor {
+                account,
+                name: name_formatted,
+                enabled: true,
+            };
+            self.auditors.insert(account, &auditor);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::AuditorCreate(AuditorCreate {
+                    account,
+                    name: auditor.name.clone(),
+                }),
+            );
+
+            Ok(auditor)
+        }
+
+        // Disabling an auditor stops them from attesting, existing attestations are kept.
+        #[ink(message)]
+        pub fn auditors_update(
+            &mut self,
+            account: AccountId,
+            name: String,
+            enabled: bool,
+        ) -> Result<Auditor> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+            let mut auditor: Auditor = self.auditors_show(account)?;
+            let name_formatted: String = name.trim().to_string();
+            Self::validate_presence_of(&name_formatted, "Name")?;
+
+            auditor.name = name_formatted;
+            auditor.enabled = enabled;
+            self.auditors.insert(account, &auditor);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::AuditorUpdate(AuditorUpdate {
+                    account,
+                    name: auditor.name.clone(),
+                    enabled,
+                }),
+            );
+
+            Ok(auditor)
+        }
+
+        // The caller is verified by the smart contract returning the caller from `Ownable::owner`.
+        // Contracts don't expose who deployed them, so smart contracts without this message
+        // (e.g. ones not built with OpenBrush's ownable) can only be made canonical by moderators.
+        // Only smart contracts on the same chain as this contract can be verified.
+        #[ink(message)]
+        pub fn claim_canonical(&mut self, id: u32) -> Result<SmartContract> {
+            let smart_contract: SmartContract = self.show(id)?;
+            let caller: AccountId = Self::env().caller();
+            Self::authorise(smart_contract.caller, caller)?;
+            if smart_contract.chain != self.chain {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contract is on a different chain".to_string(),
+                ));
+            }
+            const OWNER_SELECTOR: [u8; 4] = ink::selector_bytes!("Ownable::owner");
+            let result = build_call::<Environment>()
+                .call(smart_contract.smart_contract_address)
+                .exec_input(ExecutionInput::new(Selector::new(OWNER_SELECTOR)))
+                .returns::<Option<AccountId>>()
+                .try_invoke();
+            if !matches!(result, Ok(Ok(Some(owner))) if owner == caller) {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Caller is not the owner of the smart contract".to_string(),
+                ));
+            }
+
+            self.set_canonical(
+                smart_contract.chain,
+                smart_contract.smart_contract_address,
+                Some(id),
+            );
+
+            Ok(smart_contract)
+        }
+
+        #[ink(message)]
+        pub fn clear_flags(&mut self, id: u32) -> Result<SmartContract> {
+            self.authorise_role(HubRole::Moderator, Self::env().caller())?;
+            let mut smart_contract: SmartContract = self.show(id)?;
+
+            let flag_round: u32 = self.flag_rounds.get(id).unwrap_or(0);
+            self.flag_rounds
+                .insert(id, &flag_round.checked_add(1).unwrap());
+            smart_contract.flags_count = 0;
+            self.smart_contracts.insert(id, &smart_contract);
+
+            // emit event
+            Self::emit_event(self.env(), Event::ClearFlags(ClearFlags { id }));
+
+            Ok(smart_contract)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        #[ink(message, payable)]
+        pub fn create(
+            &mut self,
+            smart_contract_address: AccountId,
+            chain: u8,
+            azero_id: Option<String>,
+            abi_url: String,
             contract_url: Option<String>,
             wasm_url: Option<String>,
             audit_url: Option<String>,
@@ -161,19 +923,28 @@ mod az_smart_contract_hub {
             project_website: Option<String>,
             github: Option<String>,
         ) -> Result<SmartContract> {
+            if self.import_open {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Import is in progress".to_string(),
+                ));
+            }
             if self.smart_contracts_count == u32::MAX {
                 return Err(AZSmartContractHubError::UnprocessableEntity(
                     "Smart contract limit reached".to_string(),
                 ));
             }
             let caller: AccountId = Self::env().caller();
+            let azero_id: String = match azero_id {
+                Some(azero_id_unwrapped) => azero_id_unwrapped,
+                None => self.primary_azero_id(caller)?,
+            };
             self.validate_ownership_of_azero_id(azero_id.clone(), caller)?;
             if let Some(group_id_unwrapped) = group_id {
-                self.validate_membership(group_id_unwrapped, caller)?;
+                self.authorise_group(group_id_unwrapped, caller)?;
             }
             let abi_url_formatted: String = self.format_url(abi_url);
             Self::validate_presence_of(&abi_url_formatted, "Link to abi")?;
-            if self.env().transferred_value() != self.fee {
+            if self.env().transferred_value() < self.fee {
                 return Err(AZSmartContractHubError::UnprocessableEntity(
                     "Incorrect fee".to_string(),
                 ));
@@ -194,9 +965,26 @@ mod az_smart_contract_hub {
                 project_name: project_name.clone(),
                 project_website: project_website.clone(),
                 github: github.clone(),
+                tags: Vec::new(),
+                interfaces: Vec::new(),
+                endorsements_count: 0,
+                flags_count: 0,
+                hidden: false,
+                created_at: Self::env().block_timestamp(),
+                created_at_block: Self::env().block_number(),
+                updated_at: Self::env().block_timestamp(),
+                updated_at_block: Self::env().block_number(),
             };
+            self.settle_deposit(&smart_contract, self.env().transferred_value() - self.fee)?;
             self.smart_contracts
                 .insert(self.smart_contracts_count, &smart_contract);
+            Self::add_to_paged_index(
+                &mut self.smart_contract_ids_by_address,
+                &mut self.smart_contract_positions_by_address,
+                &mut self.smart_contracts_count_by_address,
+                &(chain, smart_contract_address),
+                smart_contract.id,
+            );
             self.smart_contracts_count = self.smart_contracts_count.checked_add(1).unwrap();
 
             // Transfer fee to admin
@@ -225,229 +1013,3198 @@ mod az_smart_contract_hub {
                     project_name,
                     project_website,
                     github,
+                    created_at: smart_contract.created_at,
+                    created_at_block: smart_contract.created_at_block,
                 }),
             );
 
             Ok(smart_contract)
         }
 
-        #[allow(clippy::too_many_arguments)]
+        // Removes the smart contract along with its index entries and attestations.
+        // Its endorsements and flags are left in storage,
+        // where they can't be reached as ids aren't reused.
+        // The storage deposit collected at creation is refunded to the caller.
         #[ink(message)]
-        pub fn update(
-            &mut self,
-            id: u32,
-            enabled: bool,
-            azero_id: String,
-            group_id: Option<u32>,
-            audit_url: Option<String>,
-            project_name: Option<String>,
-            project_website: Option<String>,
-            github: Option<String>,
-        ) -> Result<SmartContract> {
-            let mut smart_contract: SmartContract = self.show(id)?;
+        pub fn destroy(&mut self, id: u32) -> Result<Balance> {
+            let smart_contract: SmartContract = self.show(id)?;
             let caller: AccountId = Self::env().caller();
             Self::authorise(smart_contract.caller, caller)?;
-            self.validate_ownership_of_azero_id(azero_id.clone(), caller)?;
-            if let Some(group_id_unwrapped) = group_id {
-                self.validate_membership(group_id_unwrapped, caller)?;
-            };
 
-            smart_contract.enabled = enabled;
-            smart_contract.azero_id = azero_id.clone();
-            smart_contract.group_id = group_id;
-            smart_contract.audit_url = audit_url.clone();
-            smart_contract.project_name = project_name.clone();
-            smart_contract.project_website = project_website.clone();
-            smart_contract.github = github.clone();
-            self.smart_contracts
-                .insert(smart_contract.id, &smart_contract);
+            let address_key: SmartContractAddressKey =
+                (smart_contract.chain, smart_contract.smart_contract_address);
+            if self.canonical_ids.get(address_key) == Some(id) {
+                self.set_canonical(
+                    smart_contract.chain,
+                    smart_contract.smart_contract_address,
+                    None,
+                );
+            }
+            Self::remove_from_paged_index(
+                &mut self.smart_contract_ids_by_address,
+                &mut self.smart_contract_positions_by_address,
+                &mut self.smart_contracts_count_by_address,
+                &address_key,
+                id,
+            );
+            for interface_declaration in smart_contract.interfaces.iter() {
+                Self::remove_from_paged_index(
+                    &mut self.smart_contract_ids_by_interface,
+                    &mut self.smart_contract_positions_by_interface,
+                    &mut self.smart_contracts_count_by_interface,
+                    &interface_declaration.interface_id,
+                    id,
+                );
+            }
+            for tag in smart_contract.tags.iter() {
+                Self::remove_from_paged_index(
+                    &mut self.smart_contract_ids_by_tag,
+                    &mut self.smart_contract_positions_by_tag,
+                    &mut self.smart_contracts_count_by_tag,
+                    tag,
+                    id,
+                );
+            }
+            let attestation_ids: Vec<u32> = Self::indexed_ids(
+                &self.attestation_ids_by_smart_contract,
+                &self.attestations_count_by_smart_contract,
+                id,
+            )
+            .collect();
+            for (position, attestation_id) in attestation_ids.into_iter().enumerate() {
+                if let Some(attestation) = self.attestations.take(attestation_id) {
+                    Self::remove_from_paged_index(
+                        &mut self.attestation_ids_by_auditor,
+                        &mut self.attestation_positions_by_auditor,
+                        &mut self.attestations_count_by_auditor,
+                        &attestation.auditor,
+                        attestation_id,
+                    );
+                }
+                self.attestation_ids_by_smart_contract
+                    .remove((id, position as u32));
+                self.attestation_positions_by_smart_contract
+                    .remove((id, attestation_id));
+            }
+            self.attestations_count_by_smart_contract.remove(id);
+            self.azero_id_statuses.remove(id);
+            self.flag_rounds.remove(id);
+            self.smart_contracts.remove(id);
+
+            // Refund storage deposit to caller
+            let deposit: Balance = self.deposits.take(id).unwrap_or(0);
+            if deposit > 0 && self.env().transfer(caller, deposit).is_err() {
+                panic!(
+                    "requested transfer failed. this can be the case if the contract does not\
+                     have sufficient free funds or if the transfer would have brought the\
+                     contract's balance below minimum balance."
+                )
+            }
 
             // emit event
             Self::emit_event(
                 self.env(),
-                Event::Update(Update {
-                    id: smart_contract.id,
-                    enabled: smart_contract.enabled,
-                    azero_id,
-                    group_id,
-                    project_name,
-                    project_website,
-                    github,
-                    audit_url,
+                Event::Destroy(Destroy {
+                    id,
+                    caller,
+                    deposit,
                 }),
             );
 
-            Ok(smart_contract)
+            Ok(deposit)
         }
 
+        // If endorsements require an AZERO.ID, the caller must own the one provided.
         #[ink(message)]
-        pub fn update_fee(&mut self, fee: Balance) -> Result<Balance> {
-            Self::authorise(self.admin, Self::env().caller())?;
+        pub fn endorse(&mut self, id: u32, azero_id: Option<String>) -> Result<SmartContract> {
+            let mut smart_contract: SmartContract = self.show(id)?;
+            let user: AccountId = Self::env().caller();
+            if self.endorsements.contains((id, user)) {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Endorsement has already been taken".to_string(),
+                ));
+            }
+            if let Some(azero_id_unwrapped) = azero_id {
+                self.validate_ownership_of_azero_id(azero_id_unwrapped, user)?;
+            } else if self.endorsements_require_azero_id {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "AZERO.ID can't be blank".to_string(),
+                ));
+            }
 
-            self.fee = fee;
+            self.endorsements.insert((id, user), &());
+            smart_contract.endorsements_count =
+                smart_contract.endorsements_count.checked_add(1).unwrap();
+            self.smart_contracts.insert(id, &smart_contract);
 
-            Ok(self.fee)
+            // emit event
+            Self::emit_event(self.env(), Event::Endorse(Endorse { id, user }));
+
+            Ok(smart_contract)
         }
 
-        fn authorise(allowed: AccountId, received: AccountId) -> Result<()> {
-            if allowed != received {
-                return Err(AZSmartContractHubError::Unauthorised);
+        // Each account can flag a smart contract once, until its flags are cleared.
+        #[ink(message)]
+        pub fn flags_create(
+            &mut self,
+            smart_contract_id: u32,
+            reason: FlagReason,
+            text: Option<String>,
+        ) -> Result<Flag> {
+            let mut smart_contract: SmartContract = self.show(smart_contract_id)?;
+            let user: AccountId = Self::env().caller();
+            let flag_key: FlagKey = self.flag_key(smart_contract_id, user);
+            if self.flags.contains(flag_key) {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Flag has already been taken".to_string(),
+                ));
             }
+            let text_formatted: Option<String> = text
+                .map(|text| text.trim().to_string())
+                .filter(|text| !text.is_empty());
+            if let Some(ref text_unwrapped) = text_formatted {
+                if text_unwrapped.len() > FLAG_TEXT_LENGTH_LIMIT {
+                    return Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                        "Text can't be longer than {FLAG_TEXT_LENGTH_LIMIT} bytes"
+                    )));
+                }
+            }
+
+            let flag: Flag = Flag {
+                reason,
+                text: text_formatted,
+            };
+            self.flags.insert(flag_key, &flag);
+            smart_contract.flags_count = smart_contract.flags_count.checked_add(1).unwrap();
+            self.smart_contracts
+                .insert(smart_contract_id, &smart_contract);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::FlagCreate(FlagCreate {
+                    smart_contract_id,
+                    user,
+                    reason: flag.reason.clone(),
+                    text: flag.text.clone(),
+                }),
+            );
+
+            Ok(flag)
+        }
+
+        // Closes the import so that records can be created. This can't be undone.
+        #[ink(message)]
+        pub fn finalise_import(&mut self) -> Result<()> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+
+            self.import_open = false;
 
             Ok(())
         }
 
-        // 1. For unit-testing always return the caller.
-        // 2. For e2e-testing, I can't write integration tests as the azero.id contract is private.
-        // Test different situations safely by returning results based on an azero_id_router_address that is impossible in production
-        // with azero.ids that are not allowed in production.
-        fn address_by_azero_id(&self, domain: String) -> Result<AccountId> {
-            match cfg!(test) {
-                true => Ok(self.env().caller()),
-                false => {
-                    if self.azero_id_router_address
-                        == AccountId::try_from(*b"xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx").unwrap()
-                    {
-                        if domain == *MOCK_VALID_AZERO_ID {
-                            Ok(self.env().caller())
-                        } else if domain == *MOCK_INVALID_AZERO_ID {
-                            Ok(self.env().account_id())
-                        } else {
-                            Err(AZSmartContractHubError::NotFound("Domain".to_string()))
-                        }
-                    } else {
-                        const GET_ADDRESS_SELECTOR: [u8; 4] = ink::selector_bytes!("get_address");
-                        let result = build_call::<Environment>()
-                            .call(self.azero_id_router_address)
-                            .exec_input(
-                                ExecutionInput::new(Selector::new(GET_ADDRESS_SELECTOR))
-                                    .push_arg(domain),
-                            )
-                            .returns::<core::result::Result<AccountId, u8>>()
-                            .params()
-                            .invoke();
-                        // Use the result as per the need
-                        if let Ok(address) = result {
-                            Ok(address)
-                        } else {
-                            Err(AZSmartContractHubError::NotFound("Domain".to_string()))
-                        }
-                    }
-                }
+        #[ink(message)]
+        pub fn grant_role(&mut self, role: HubRole, account: AccountId) -> Result<()> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+            if self.has_role(role, account) {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Role has already been granted".to_string(),
+                ));
             }
-        }
 
-        fn emit_event<EE: EmitEvent<Self>>(emitter: EE, event: Event) {
-            emitter.emit_event(event);
-        }
+            self.roles.insert((role, account), &());
 
-        fn format_url(&self, url: String) -> String {
-            url.trim().to_string()
+            // emit event
+            Self::emit_event(self.env(), Event::RoleGrant(RoleGrant { role, account }));
+
+            Ok(())
         }
 
-        // For unit-testing always return Ok.
-        fn validate_membership(&self, group_id: u32, account: AccountId) -> Result<Role> {
-            match cfg!(test) {
-                true => Ok(Role::Member),
-                false => {
-                    const VALIDATE_MEMBERSHIP_SELECTOR: [u8; 4] =
-                        ink::selector_bytes!("validate_membership");
-                    Ok(build_call::<Environment>()
-                        .call(self.az_groups_address)
-                        .exec_input(
-                            ExecutionInput::new(Selector::new(VALIDATE_MEMBERSHIP_SELECTOR))
-                                .push_arg(group_id)
-                                .push_arg(account),
-                        )
-                        .returns::<core::result::Result<Role, AZGroupsError>>()
-                        .invoke()?)
+        // Records from the original deployment must be imported in id order, before the import is finalised.
+        // Their timestamps and block numbers are unknown and are set to zero.
+        #[ink(message)]
+        pub fn import(&mut self, smart_contracts: Vec<SmartContractV0>) -> Result<u32> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+            if !self.import_open {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Import has been finalised".to_string(),
+                ));
+            }
+
+            for smart_contract_v0 in smart_contracts {
+                if smart_contract_v0.id != self.smart_contracts_count {
+                    return Err(AZSmartContractHubError::UnprocessableEntity(
+                        "Smart contracts must be imported in order".to_string(),
+                    ));
                 }
+
+                let smart_contract: SmartContract = SmartContract {
+                    id: smart_contract_v0.id,
+                    smart_contract_address: smart_contract_v0.smart_contract_address,
+                    chain: smart_contract_v0.chain,
+                    caller: smart_contract_v0.caller,
+                    enabled: smart_contract_v0.enabled,
+                    azero_id: smart_contract_v0.azero_id,
+                    abi_url: smart_contract_v0.abi_url,
+                    contract_url: smart_contract_v0.contract_url,
+                    wasm_url: smart_contract_v0.wasm_url,
+                    audit_url: smart_contract_v0.audit_url,
+                    group_id: smart_contract_v0.group_id,
+                    project_name: smart_contract_v0.project_name,
+                    project_website: smart_contract_v0.project_website,
+                    github: smart_contract_v0.github,
+                    tags: Vec::new(),
+                    interfaces: Vec::new(),
+                    endorsements_count: 0,
+                    flags_count: 0,
+                    hidden: false,
+                    created_at: 0,
+                    created_at_block: 0,
+                    updated_at: 0,
+                    updated_at_block: 0,
+                };
+                self.smart_contracts
+                    .insert(smart_contract.id, &smart_contract);
+                Self::add_to_paged_index(
+                    &mut self.smart_contract_ids_by_address,
+                    &mut self.smart_contract_positions_by_address,
+                    &mut self.smart_contracts_count_by_address,
+                    &(smart_contract.chain, smart_contract.smart_contract_address),
+                    smart_contract.id,
+                );
+                self.smart_contracts_count = self.smart_contracts_count.checked_add(1).unwrap();
+
+                // emit event
+                Self::emit_event(
+                    self.env(),
+                    Event::Create(Create {
+                        id: smart_contract.id,
+                        smart_contract_address: smart_contract.smart_contract_address,
+                        chain: smart_contract.chain,
+                        caller: smart_contract.caller,
+                        azero_id: smart_contract.azero_id,
+                        abi_url: smart_contract.abi_url,
+                        contract_url: smart_contract.contract_url,
+                        wasm_url: smart_contract.wasm_url,
+                        audit_url: smart_contract.audit_url,
+                        group_id: smart_contract.group_id,
+                        project_name: smart_contract.project_name,
+                        project_website: smart_contract.project_website,
+                        github: smart_contract.github,
+                        created_at: smart_contract.created_at,
+                        created_at_block: smart_contract.created_at_block,
+                    }),
+                );
             }
+
+            Ok(self.smart_contracts_count)
         }
 
-        fn validate_ownership_of_azero_id(
-            &self,
-            azero_id: String,
-            caller: AccountId,
-        ) -> Result<()> {
-            if caller != self.address_by_azero_id(azero_id.clone())? {
+        #[ink(message)]
+        pub fn interfaces_create(
+            &mut self,
+            name: String,
+            selector: Option<[u8; 4]>,
+        ) -> Result<Interface> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+            if self.interfaces_count == u32::MAX {
                 return Err(AZSmartContractHubError::UnprocessableEntity(
-                    "Domain does not belong to caller".to_string(),
+                    "Interface limit reached".to_string(),
                 ));
             }
+            let name_formatted: String = name.trim().to_string();
+            Self::validate_presence_of(&name_formatted, "Name")?;
 
-            Ok(())
-        }
-
-        fn validate_presence_of(string: &str, field_name: &str) -> Result<()> {
-            if string.is_empty() {
-                return Err(AZSmartContractHubError::UnprocessableEntity(format!(
-                    "{field_name} can't be blank"
-                )));
+            let interface: Interface = Interface {
+                id: self.interfaces_count,
+                name: name_formatted,
+                selector,
+                selector_version: 0,
+                enabled: true,
             };
+            self.interfaces.insert(interface.id, &interface);
+            self.interfaces_count = self.interfaces_count.checked_add(1).unwrap();
 
-            Ok(())
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::InterfaceCreate(InterfaceCreate {
+                    id: interface.id,
+                    name: interface.name.clone(),
+                    selector,
+                }),
+            );
+
+            Ok(interface)
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::env::{
-            test::{default_accounts, set_caller, DefaultAccounts},
-            DefaultEnvironment,
-        };
+        // Disabling an interface stops it from being newly declared,
+        // existing declarations are kept.
+        // Changing the selector marks every declaration of the interface as unverified,
+        // by increasing the selector version rather than updating each declaration.
+        #[ink(message)]
+        pub fn interfaces_update(
+            &mut self,
+            id: u32,
+            name: String,
+            selector: Option<[u8; 4]>,
+            enabled: bool,
+        ) -> Result<Interface> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+            let mut interface: Interface = self.interfaces_show(id)?;
+            let name_formatted: String = name.trim().to_string();
+            Self::validate_presence_of(&name_formatted, "Name")?;
 
-        const MOCK_AZERO_ID: &str = "OnionKnight";
-        const MOCK_AZERO_ID_TWO: &str = "Robert Ford";
-        const MOCK_ABI_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/tmuurccd5a7lcvin6ae9.json";
-        const MOCK_CONTRACT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/vsvsvavdvavav.json";
-        const MOCK_WASM_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/ffbrgnteyjytntehthw34hhhwhwhwnq343.json";
-        const MOCK_AUDIT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/mlkmkbdsbmdsb3rrg3m.json";
-        const MOCK_PROJECT_NAME: &str = "Smart Contract Hub";
-        const MOCK_PROJECT_WEBSITE: &str = "https://someprojectwebsite.org/projects/project-name";
-        const MOCK_GITHUB: &str = "https://github.com/smart-contract-hub/project-name";
+            if interface.selector != selector {
+                interface.selector_version = interface.selector_version.checked_add(1).unwrap();
+            }
+            interface.name = name_formatted;
+            interface.selector = selector;
+            interface.enabled = enabled;
+            self.interfaces.insert(id, &interface);
 
-        // === HELPERS ===
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::InterfaceUpdate(InterfaceUpdate {
+                    id,
+                    name: interface.name.clone(),
+                    selector,
+                    enabled,
+                }),
+            );
+
+            Ok(interface)
+        }
+
+        // Anyone can check whether a smart contract's AZERO.ID still resolves to its caller.
+        #[ink(message)]
+        pub fn revalidate(&mut self, id: u32) -> Result<AzeroIdStatus> {
+            let smart_contract: SmartContract = self.show(id)?;
+            let stale: bool = match self.address_by_azero_id(smart_contract.azero_id.clone()) {
+                Ok(address) => address != smart_contract.caller,
+                Err(AZSmartContractHubError::AzeroIdRouterError(_)) => true,
+                Err(e) => return Err(e),
+            };
+            let azero_id_status: AzeroIdStatus = AzeroIdStatus {
+                azero_id: smart_contract.azero_id,
+                stale,
+                checked_at: Self::env().block_timestamp(),
+                checked_at_block: Self::env().block_number(),
+            };
+            self.azero_id_statuses.insert(id, &azero_id_status);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::Revalidate(Revalidate {
+                    id,
+                    azero_id: azero_id_status.azero_id.clone(),
+                    stale,
+                }),
+            );
+
+            Ok(azero_id_status)
+        }
+
+        // Admins can't revoke their own admin role so that there is always at least one admin.
+        #[ink(message)]
+        pub fn revoke_role(&mut self, role: HubRole, account: AccountId) -> Result<()> {
+            let caller: AccountId = Self::env().caller();
+            self.authorise_role(HubRole::Admin, caller)?;
+            if role == HubRole::Admin && account == caller {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Can't revoke own admin role".to_string(),
+                ));
+            }
+            if !self.has_role(role, account) {
+                return Err(AZSmartContractHubError::NotFound("Role".to_string()));
+            }
+
+            self.roles.remove((role, account));
+
+            // emit event
+            Self::emit_event(self.env(), Event::RoleRevoke(RoleRevoke { role, account }));
+
+            Ok(())
+        }
+
+        #[ink(message)]
+        pub fn unendorse(&mut self, id: u32) -> Result<SmartContract> {
+            let mut smart_contract: SmartContract = self.show(id)?;
+            let user: AccountId = Self::env().caller();
+            if self.endorsements.take((id, user)).is_none() {
+                return Err(AZSmartContractHubError::NotFound("Endorsement".to_string()));
+            }
+
+            smart_contract.endorsements_count =
+                smart_contract.endorsements_count.checked_sub(1).unwrap();
+            self.smart_contracts.insert(id, &smart_contract);
+
+            // emit event
+            Self::emit_event(self.env(), Event::Unendorse(Unendorse { id, user }));
+
+            Ok(smart_contract)
+        }
+
+        #[allow(clippy::too_many_arguments)]
+        #[ink(message, payable)]
+        pub fn update(
+            &mut self,
+            id: u32,
+            enabled: bool,
+            azero_id: String,
+            group_id: Option<u32>,
+            audit_url: Option<String>,
+            project_name: Option<String>,
+            project_website: Option<String>,
+            github: Option<String>,
+        ) -> Result<SmartContract> {
+            let mut smart_contract: SmartContract = self.show(id)?;
+            let caller: AccountId = Self::env().caller();
+            Self::authorise(smart_contract.caller, caller)?;
+            self.validate_ownership_of_azero_id(azero_id.clone(), caller)?;
+            if let Some(group_id_unwrapped) = group_id {
+                self.authorise_group(group_id_unwrapped, caller)?;
+            };
+
+            smart_contract.enabled = enabled;
+            smart_contract.azero_id = azero_id.clone();
+            smart_contract.group_id = group_id;
+            smart_contract.audit_url = audit_url.clone();
+            smart_contract.project_name = project_name.clone();
+            smart_contract.project_website = project_website.clone();
+            smart_contract.github = github.clone();
+            smart_contract.updated_at = Self::env().block_timestamp();
+            smart_contract.updated_at_block = Self::env().block_number();
+            self.settle_deposit(&smart_contract, self.env().transferred_value())?;
+            self.smart_contracts
+                .insert(smart_contract.id, &smart_contract);
+            self.azero_id_statuses.remove(smart_contract.id);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::Update(Update {
+                    id: smart_contract.id,
+                    enabled: smart_contract.enabled,
+                    azero_id,
+                    group_id,
+                    project_name,
+                    project_website,
+                    github,
+                    audit_url,
+                    updated_at: smart_contract.updated_at,
+                    updated_at_block: smart_contract.updated_at_block,
+                }),
+            );
+
+            Ok(smart_contract)
+        }
+
+        // A smart contract can be assigned as canonical, or the canonical smart contract can be removed.
+        #[ink(message)]
+        pub fn update_canonical(
+            &mut self,
+            chain: u8,
+            smart_contract_address: AccountId,
+            id: Option<u32>,
+        ) -> Result<Option<u32>> {
+            self.authorise_role(HubRole::Moderator, Self::env().caller())?;
+            if let Some(id_unwrapped) = id {
+                let smart_contract: SmartContract = self.show(id_unwrapped)?;
+                if smart_contract.chain != chain
+                    || smart_contract.smart_contract_address != smart_contract_address
+                {
+                    return Err(AZSmartContractHubError::UnprocessableEntity(
+                        "Smart contract does not match chain and address".to_string(),
+                    ));
+                }
+            }
+
+            self.set_canonical(chain, smart_contract_address, id);
+
+            Ok(id)
+        }
+
+        #[ink(message)]
+        pub fn update_endorsements_require_azero_id(
+            &mut self,
+            endorsements_require_azero_id: bool,
+        ) -> Result<bool> {
+            self.authorise_role(HubRole::Admin, Self::env().caller())?;
+
+            self.endorsements_require_azero_id = endorsements_require_azero_id;
+
+            Ok(self.endorsements_require_azero_id)
+        }
+
+        #[ink(message)]
+        pub fn update_fee(&mut self, fee: Balance) -> Result<Balance> {
+            self.authorise_role(HubRole::FeeManager, Self::env().caller())?;
+
+            self.fee = fee;
+
+            Ok(self.fee)
+        }
+
+        #[ink(message)]
+        pub fn update_hidden(&mut self, id: u32, hidden: bool) -> Result<SmartContract> {
+            self.authorise_role(HubRole::Moderator, Self::env().caller())?;
+            let mut smart_contract: SmartContract = self.show(id)?;
+
+            smart_contract.hidden = hidden;
+            self.smart_contracts.insert(id, &smart_contract);
+
+            // emit event
+            Self::emit_event(self.env(), Event::UpdateHidden(UpdateHidden { id, hidden }));
+
+            Ok(smart_contract)
+        }
+
+        // Verification status is kept for interfaces that remain declared.
+        #[ink(message, payable)]
+        pub fn update_interfaces(
+            &mut self,
+            id: u32,
+            interface_ids: Vec<u32>,
+        ) -> Result<SmartContract> {
+            let mut smart_contract: SmartContract = self.show(id)?;
+            Self::authorise(smart_contract.caller, Self::env().caller())?;
+            let mut interfaces: Vec<InterfaceDeclaration> = Vec::new();
+            for interface_id in interface_ids {
+                if interfaces
+                    .iter()
+                    .any(|declaration| declaration.interface_id == interface_id)
+                {
+                    continue;
+                }
+                if let Some(declaration) = smart_contract
+                    .interfaces
+                    .iter()
+                    .find(|declaration| declaration.interface_id == interface_id)
+                {
+                    interfaces.push(declaration.clone());
+                } else {
+                    let interface: Interface = self.interfaces_show(interface_id)?;
+                    if !interface.enabled {
+                        return Err(AZSmartContractHubError::UnprocessableEntity(
+                            "Interface is disabled".to_string(),
+                        ));
+                    }
+                    interfaces.push(InterfaceDeclaration {
+                        interface_id,
+                        verified: false,
+                        selector_version: interface.selector_version,
+                    });
+                }
+            }
+            if interfaces.len() > INTERFACES_LIMIT {
+                return Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                    "Interfaces limit is {INTERFACES_LIMIT}"
+                )));
+            }
+
+            // Update interface index
+            let interface_ids: Vec<u32> = interfaces
+                .iter()
+                .map(|declaration| declaration.interface_id)
+                .collect();
+            for declaration in smart_contract.interfaces.iter() {
+                if !interface_ids.contains(&declaration.interface_id) {
+                    Self::remove_from_paged_index(
+                        &mut self.smart_contract_ids_by_interface,
+                        &mut self.smart_contract_positions_by_interface,
+                        &mut self.smart_contracts_count_by_interface,
+                        &declaration.interface_id,
+                        id,
+                    );
+                }
+            }
+            for interface_id in interface_ids.iter() {
+                if !smart_contract
+                    .interfaces
+                    .iter()
+                    .any(|declaration| declaration.interface_id == *interface_id)
+                {
+                    Self::add_to_paged_index(
+                        &mut self.smart_contract_ids_by_interface,
+                        &mut self.smart_contract_positions_by_interface,
+                        &mut self.smart_contracts_count_by_interface,
+                        interface_id,
+                        id,
+                    );
+                }
+            }
+            smart_contract.interfaces = interfaces;
+            self.settle_deposit(&smart_contract, self.env().transferred_value())?;
+            self.smart_contracts.insert(id, &smart_contract);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::UpdateInterfaces(UpdateInterfaces { id, interface_ids }),
+            );
+
+            Ok(smart_contract)
+        }
+
+        #[ink(message)]
+        pub fn update_storage_deposit(&mut self, storage_deposit: Balance) -> Result<Balance> {
+            self.authorise_role(HubRole::FeeManager, Self::env().caller())?;
+
+            self.storage_deposit = storage_deposit;
+
+            Ok(self.storage_deposit)
+        }
+
+        #[ink(message)]
+        pub fn update_storage_deposit_per_byte(
+            &mut self,
+            storage_deposit_per_byte: Balance,
+        ) -> Result<Balance> {
+            self.authorise_role(HubRole::FeeManager, Self::env().caller())?;
+
+            self.storage_deposit_per_byte = storage_deposit_per_byte;
+
+            Ok(self.storage_deposit_per_byte)
+        }
+
+        // Tags are trimmed, lowercased and deduplicated before being stored.
+        #[ink(message, payable)]
+        pub fn update_tags(&mut self, id: u32, tags: Vec<String>) -> Result<SmartContract> {
+            let mut smart_contract: SmartContract = self.show(id)?;
+            Self::authorise(smart_contract.caller, Self::env().caller())?;
+            let mut formatted_tags: Vec<String> = Vec::new();
+            for tag in tags {
+                let formatted_tag: String = Self::format_tag(tag);
+                Self::validate_presence_of(&formatted_tag, "Tag")?;
+                if formatted_tag.len() > TAG_LENGTH_LIMIT {
+                    return Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                        "Tag can't be longer than {TAG_LENGTH_LIMIT} bytes"
+                    )));
+                }
+                if !formatted_tags.contains(&formatted_tag) {
+                    formatted_tags.push(formatted_tag);
+                }
+            }
+            if formatted_tags.len() > TAGS_LIMIT {
+                return Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                    "Tags limit is {TAGS_LIMIT}"
+                )));
+            }
+
+            // Update tag index
+            for tag in smart_contract.tags.iter() {
+                if !formatted_tags.contains(tag) {
+                    Self::remove_from_paged_index(
+                        &mut self.smart_contract_ids_by_tag,
+                        &mut self.smart_contract_positions_by_tag,
+                        &mut self.smart_contracts_count_by_tag,
+                        tag,
+                        id,
+                    );
+                }
+            }
+            for tag in formatted_tags.iter() {
+                if !smart_contract.tags.contains(tag) {
+                    Self::add_to_paged_index(
+                        &mut self.smart_contract_ids_by_tag,
+                        &mut self.smart_contract_positions_by_tag,
+                        &mut self.smart_contracts_count_by_tag,
+                        tag,
+                        id,
+                    );
+                }
+            }
+            smart_contract.tags = formatted_tags.clone();
+            self.settle_deposit(&smart_contract, self.env().transferred_value())?;
+            self.smart_contracts.insert(id, &smart_contract);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::UpdateTags(UpdateTags {
+                    id,
+                    tags: formatted_tags,
+                }),
+            );
+
+            Ok(smart_contract)
+        }
+
+        // Only smart contracts on the same chain as this contract can be verified.
+        // The interface's selector is called on the smart contract and the declaration
+        // is verified if the call is dispatched successfully.
+        // The output isn't decoded as its type depends on the interface,
+        // so a message that returns a Result::Err still counts as implemented.
+        #[ink(message)]
+        pub fn verify_interface(&mut self, id: u32, interface_id: u32) -> Result<SmartContract> {
+            let mut smart_contract: SmartContract = self.show(id)?;
+            let declaration_index: usize = smart_contract
+                .interfaces
+                .iter()
+                .position(|declaration| declaration.interface_id == interface_id)
+                .ok_or(AZSmartContractHubError::NotFound(
+                    "InterfaceDeclaration".to_string(),
+                ))?;
+            if smart_contract.chain != self.chain {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contract is on a different chain".to_string(),
+                ));
+            }
+            let interface: Interface = self.interfaces_show(interface_id)?;
+            let selector: [u8; 4] =
+                interface
+                    .selector
+                    .ok_or(AZSmartContractHubError::UnprocessableEntity(
+                        "Interface can't be verified".to_string(),
+                    ))?;
+            let result = build_call::<Environment>()
+                .call(smart_contract.smart_contract_address)
+                .exec_input(ExecutionInput::new(Selector::new(selector)))
+                .returns::<()>()
+                .try_invoke();
+            if !matches!(result, Ok(Ok(()))) {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contract does not implement interface".to_string(),
+                ));
+            }
+
+            smart_contract.interfaces[declaration_index].verified = true;
+            smart_contract.interfaces[declaration_index].selector_version =
+                interface.selector_version;
+            self.smart_contracts.insert(id, &smart_contract);
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::VerifyInterface(VerifyInterface { id, interface_id }),
+            );
+
+            Ok(smart_contract)
+        }
+
+        // Paged indexes store one entry per id, with a count per key and the position of each id,
+        // so that adding or removing an id costs the same however many ids share the key.
+        fn add_to_paged_index<
+            K: scale::EncodeLike + Clone,
+            IdsKey: StorageKey,
+            PositionsKey: StorageKey,
+            CountsKey: StorageKey,
+        >(
+            ids: &mut Mapping<(K, u32), u32, IdsKey>,
+            positions: &mut Mapping<(K, u32), u32, PositionsKey>,
+            counts: &mut Mapping<K, u32, CountsKey>,
+            key: &K,
+            id: u32,
+        ) {
+            let count: u32 = counts.get(key).unwrap_or(0);
+            ids.insert((key.clone(), count), &id);
+            positions.insert((key.clone(), id), &count);
+            counts.insert(key, &count.checked_add(1).unwrap());
+        }
+
+        // Linking a smart contract to a group requires the account to be a member of the group
+        // who can manage the group's hub records.
+        fn authorise_group(&self, group_id: u32, account: AccountId) -> Result<()> {
+            let membership_checker = self.membership_checker();
+            membership_checker.validate_membership(group_id, account)?;
+            if !membership_checker.has_permission(
+                group_id,
+                account,
+                Permission::ManageHubRecords,
+            )? {
+                return Err(AZSmartContractHubError::Unauthorised);
+            }
+
+            Ok(())
+        }
+
+        fn authorise(allowed: AccountId, received: AccountId) -> Result<()> {
+            if allowed != received {
+                return Err(AZSmartContractHubError::Unauthorised);
+            }
+
+            Ok(())
+        }
+
+        fn authorise_role(&self, role: HubRole, account: AccountId) -> Result<()> {
+            if !self.has_role(role, account) && !self.has_role(HubRole::Admin, account) {
+                return Err(AZSmartContractHubError::Unauthorised);
+            }
+
+            Ok(())
+        }
+
+        fn address_by_azero_id(&self, domain: String) -> Result<AccountId> {
+            #[cfg(test)]
+            let result = crate::azero_id_router::in_memory::get_address(domain);
+            #[cfg(not(test))]
+            let result = {
+                let identity_resolver: contract_ref!(IdentityResolver) =
+                    self.azero_id_router_address.into();
+                identity_resolver
+                    .call()
+                    .get_address(domain)
+                    .try_invoke()??
+            };
+
+            result.map_err(AZSmartContractHubError::AzeroIdRouterError)
+        }
+
+        fn emit_event<EE: EmitEvent<Self>>(emitter: EE, event: Event) {
+            emitter.emit_event(event);
+        }
+
+        fn flag_key(&self, smart_contract_id: u32, user: AccountId) -> FlagKey {
+            (
+                smart_contract_id,
+                self.flag_rounds.get(smart_contract_id).unwrap_or(0),
+                user,
+            )
+        }
+
+        fn format_tag(tag: String) -> String {
+            tag.trim().to_lowercase()
+        }
+
+        fn format_url(&self, url: String) -> String {
+            url.trim().to_string()
+        }
+
+        #[cfg(not(test))]
+        fn membership_checker(&self) -> AZGroupsMembershipChecker {
+            AZGroupsMembershipChecker {
+                az_groups_address: self.az_groups_address,
+            }
+        }
+
+        #[cfg(test)]
+        fn membership_checker(&self) -> InMemoryMembershipChecker {
+            InMemoryMembershipChecker
+        }
+
+        fn indexed_ids<
+            'a,
+            K: scale::EncodeLike + Clone + 'a,
+            IdsKey: StorageKey,
+            CountsKey: StorageKey,
+        >(
+            ids: &'a Mapping<(K, u32), u32, IdsKey>,
+            counts: &Mapping<K, u32, CountsKey>,
+            key: K,
+        ) -> impl Iterator<Item = u32> + 'a {
+            (0..counts.get(&key).unwrap_or(0))
+                .filter_map(move |position| ids.get((key.clone(), position)))
+        }
+
+        // Only the ids on the page are read.
+        fn paged_ids<
+            'a,
+            K: scale::EncodeLike + Clone + 'a,
+            IdsKey: StorageKey,
+            CountsKey: StorageKey,
+        >(
+            ids: &'a Mapping<(K, u32), u32, IdsKey>,
+            counts: &Mapping<K, u32, CountsKey>,
+            key: K,
+            page: u32,
+            size: u8,
+        ) -> impl Iterator<Item = u32> + 'a {
+            (0..counts.get(&key).unwrap_or(0))
+                .skip((page as usize).saturating_mul(size.into()))
+                .take(size.into())
+                .filter_map(move |position| ids.get((key.clone(), position)))
+        }
+
+        // Reverse resolution of an account to its primary AZERO.ID.
+        fn primary_azero_id(&self, account: AccountId) -> Result<String> {
+            #[cfg(test)]
+            let primary_domains = crate::azero_id_router::in_memory::get_primary_domains(account);
+            #[cfg(not(test))]
+            let primary_domains = {
+                let identity_resolver: contract_ref!(IdentityResolver) =
+                    self.azero_id_router_address.into();
+                identity_resolver
+                    .call()
+                    .get_primary_domains(account, None)
+                    .try_invoke()??
+            };
+
+            primary_domains
+                .into_iter()
+                .next()
+                .map(|(_registry, domain)| domain)
+                .ok_or(AZSmartContractHubError::NotFound(
+                    "PrimaryDomain".to_string(),
+                ))
+        }
+
+        // The last id of the key is moved into the position of the removed id.
+        fn remove_from_paged_index<
+            K: scale::EncodeLike + Clone,
+            IdsKey: StorageKey,
+            PositionsKey: StorageKey,
+            CountsKey: StorageKey,
+        >(
+            ids: &mut Mapping<(K, u32), u32, IdsKey>,
+            positions: &mut Mapping<(K, u32), u32, PositionsKey>,
+            counts: &mut Mapping<K, u32, CountsKey>,
+            key: &K,
+            id: u32,
+        ) {
+            let Some(position) = positions.take((key.clone(), id)) else {
+                return;
+            };
+            let last_position: u32 = counts.get(key).unwrap_or(1).saturating_sub(1);
+            if let Some(last_id) = ids.take((key.clone(), last_position)) {
+                if position != last_position {
+                    ids.insert((key.clone(), position), &last_id);
+                    positions.insert((key.clone(), last_id), &position);
+                }
+            }
+            if last_position == 0 {
+                counts.remove(key);
+            } else {
+                counts.insert(key, &last_position);
+            }
+        }
+
+        fn set_canonical(&mut self, chain: u8, smart_contract_address: AccountId, id: Option<u32>) {
+            if let Some(id_unwrapped) = id {
+                self.canonical_ids
+                    .insert((chain, smart_contract_address), &id_unwrapped);
+            } else {
+                self.canonical_ids.remove((chain, smart_contract_address));
+            }
+
+            // emit event
+            Self::emit_event(
+                self.env(),
+                Event::UpdateCanonical(UpdateCanonical {
+                    chain,
+                    smart_contract_address,
+                    id,
+                }),
+            );
+        }
+
+        // The deposit held for a smart contract is the storage deposit plus the storage deposit per byte
+        // of the encoded smart contract. Any amount paid over the required deposit, including
+        // the difference when a smart contract shrinks, is refunded to the caller.
+        fn settle_deposit(&mut self, smart_contract: &SmartContract, paid: Balance) -> Result<()> {
+            let required: Balance = self
+                .storage_deposit_per_byte
+                .checked_mul(scale::Encode::encoded_size(smart_contract) as Balance)
+                .and_then(|deposit| deposit.checked_add(self.storage_deposit))
+                .ok_or(AZSmartContractHubError::UnprocessableEntity(
+                    "Deposit is too large".to_string(),
+                ))?;
+            let available: Balance = self
+                .deposits
+                .get(smart_contract.id)
+                .unwrap_or(0)
+                .checked_add(paid)
+                .ok_or(AZSmartContractHubError::UnprocessableEntity(
+                    "Deposit is too large".to_string(),
+                ))?;
+            if available < required {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Insufficient deposit".to_string(),
+                ));
+            }
+
+            if required > 0 {
+                self.deposits.insert(smart_contract.id, &required);
+            } else {
+                self.deposits.remove(smart_contract.id);
+            }
+            let refund: Balance = available - required;
+            if refund > 0 && self.env().transfer(Self::env().caller(), refund).is_err() {
+                panic!(
+                    "requested transfer failed. this can be the case if the contract does not\
+                     have sufficient free funds or if the transfer would have brought the\
+                     contract's balance below minimum balance."
+                )
+            }
+
+            Ok(())
+        }
+
+        // Declarations verified against an earlier selector version are returned as unverified.
+        fn smart_contract(&self, id: u32) -> Option<SmartContract> {
+            let mut smart_contract: SmartContract = self.smart_contracts.get(id)?;
+            for declaration in smart_contract.interfaces.iter_mut() {
+                if declaration.verified
+                    && self
+                        .interfaces
+                        .get(declaration.interface_id)
+                        .is_none_or(|interface| {
+                            interface.selector_version != declaration.selector_version
+                        })
+                {
+                    declaration.verified = false;
+                }
+            }
+
+            Some(smart_contract)
+        }
+
+        // Hidden smart contracts are skipped before paging.
+        fn smart_contracts_by_ids(
+            &self,
+            ids: impl IntoIterator<Item = u32>,
+            page: u32,
+            size: u8,
+        ) -> Vec<SmartContract> {
+            ids.into_iter()
+                .filter_map(|id| self.smart_contract(id))
+                .filter(|smart_contract| !smart_contract.hidden)
+                .skip((page as usize).saturating_mul(size.into()))
+                .take(size.into())
+                .collect()
+        }
+
+        fn validate_ownership_of_azero_id(
+            &self,
+            azero_id: String,
+            caller: AccountId,
+        ) -> Result<()> {
+            if caller != self.address_by_azero_id(azero_id.clone())? {
+                return Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Domain does not belong to caller".to_string(),
+                ));
+            }
+
+            Ok(())
+        }
+
+        fn validate_presence_of(string: &str, field_name: &str) -> Result<()> {
+            if string.is_empty() {
+                return Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                    "{field_name} can't be blank"
+                )));
+            };
+
+            Ok(())
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::{azero_id_router, membership_checker::in_memory};
+        use az_groups_types::{AZGroupsError, Role};
+        use ink::env::{
+            test::{default_accounts, set_caller, DefaultAccounts},
+            DefaultEnvironment,
+        };
+
+        const MOCK_AZERO_ID: &str = "OnionKnight";
+        const MOCK_AZERO_ID_TWO: &str = "Robert Ford";
+        const MOCK_ABI_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/tmuurccd5a7lcvin6ae9.json";
+        const MOCK_CONTRACT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/vsvsvavdvavav.json";
+        const MOCK_WASM_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/ffbrgnteyjytntehthw34hhhwhwhwnq343.json";
+        const MOCK_AUDIT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/mlkmkbdsbmdsb3rrg3m.json";
+        const MOCK_PROJECT_NAME: &str = "Smart Contract Hub";
+        const MOCK_PROJECT_WEBSITE: &str = "https://someprojectwebsite.org/projects/project-name";
+        const MOCK_GITHUB: &str = "https://github.com/smart-contract-hub/project-name";
+
+        // === HELPERS ===
         fn init() -> (DefaultAccounts<DefaultEnvironment>, AZSmartContractHub) {
             let accounts = default_accounts();
             set_caller::<DefaultEnvironment>(accounts.bob);
-            let az_smart_contract_hub = AZSmartContractHub::new(accounts.eve, accounts.frank);
-            (accounts, az_smart_contract_hub)
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.bob));
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID_TWO, Some(accounts.bob));
+            azero_id_router::in_memory::set_primary_domain(accounts.bob, MOCK_AZERO_ID);
+            let mut az_smart_contract_hub =
+                AZSmartContractHub::new(accounts.eve, accounts.frank, 0);
+            az_smart_contract_hub.finalise_import().unwrap();
+            (accounts, az_smart_contract_hub)
+        }
+
+        fn create_smart_contract(
+            az_smart_contract_hub: &mut AZSmartContractHub,
+            smart_contract_address: AccountId,
+        ) -> SmartContract {
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(
+                az_smart_contract_hub.fee + az_smart_contract_hub.storage_deposit,
+            );
+            az_smart_contract_hub
+                .create(
+                    smart_contract_address,
+                    0,
+                    Some(MOCK_AZERO_ID.to_string()),
+                    MOCK_ABI_URL.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap()
+        }
+
+        fn smart_contract_ids_by_interface(
+            az_smart_contract_hub: &AZSmartContractHub,
+            interface_id: u32,
+        ) -> Vec<u32> {
+            AZSmartContractHub::indexed_ids(
+                &az_smart_contract_hub.smart_contract_ids_by_interface,
+                &az_smart_contract_hub.smart_contracts_count_by_interface,
+                interface_id,
+            )
+            .collect()
+        }
+
+        fn smart_contract_ids_by_address(
+            az_smart_contract_hub: &AZSmartContractHub,
+            smart_contract_address: AccountId,
+        ) -> Vec<u32> {
+            AZSmartContractHub::indexed_ids(
+                &az_smart_contract_hub.smart_contract_ids_by_address,
+                &az_smart_contract_hub.smart_contracts_count_by_address,
+                (0, smart_contract_address),
+            )
+            .collect()
+        }
+
+        fn smart_contract_ids_by_tag(
+            az_smart_contract_hub: &AZSmartContractHub,
+            tag: &str,
+        ) -> Vec<u32> {
+            AZSmartContractHub::indexed_ids(
+                &az_smart_contract_hub.smart_contract_ids_by_tag,
+                &az_smart_contract_hub.smart_contracts_count_by_tag,
+                tag.to_string(),
+            )
+            .collect()
+        }
+
+        fn create_attestation(
+            az_smart_contract_hub: &mut AZSmartContractHub,
+            smart_contract_id: u32,
+        ) -> Attestation {
+            az_smart_contract_hub
+                .attestations_create(
+                    smart_contract_id,
+                    MOCK_AUDIT_URL.to_string(),
+                    Hash::from([1; 32]),
+                    Hash::from([2; 32]),
+                    Verdict::Passed,
+                )
+                .unwrap()
+        }
+
+        // === TESTS ===
+        // === TEST QUERIES ===
+        #[ink::test]
+        fn test_attestations_by_auditor() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when auditor has no attestations
+            // = * it returns an empty vector
+            assert_eq!(
+                az_smart_contract_hub.attestations_by_auditor(accounts.charlie, 0, 10),
+                vec![]
+            );
+            // = when auditor has attestations
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            for account in [accounts.bob, accounts.charlie] {
+                az_smart_contract_hub
+                    .auditors_create(account, "Auditor".to_string())
+                    .unwrap();
+            }
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            create_attestation(&mut az_smart_contract_hub, 0);
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            create_attestation(&mut az_smart_contract_hub, 0);
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            create_attestation(&mut az_smart_contract_hub, 1);
+            // = * it returns the requested page of the auditor's attestations
+            let mut result: Vec<Attestation> =
+                az_smart_contract_hub.attestations_by_auditor(accounts.charlie, 0, 1);
+            assert_eq!(result.iter().map(|a| a.id).collect::<Vec<u32>>(), vec![0]);
+            result = az_smart_contract_hub.attestations_by_auditor(accounts.charlie, 1, 1);
+            assert_eq!(result.iter().map(|a| a.id).collect::<Vec<u32>>(), vec![2]);
+        }
+
+        #[ink::test]
+        fn test_attestations_by_smart_contract() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when smart contract has no attestations
+            // = * it returns an empty vector
+            assert_eq!(
+                az_smart_contract_hub.attestations_by_smart_contract(0, 0, 10),
+                vec![]
+            );
+            // = when smart contract has attestations
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            az_smart_contract_hub
+                .auditors_create(accounts.bob, "Auditor".to_string())
+                .unwrap();
+            create_attestation(&mut az_smart_contract_hub, 1);
+            create_attestation(&mut az_smart_contract_hub, 0);
+            create_attestation(&mut az_smart_contract_hub, 1);
+            // = * it returns the requested page of the smart contract's attestations
+            let result: Vec<Attestation> =
+                az_smart_contract_hub.attestations_by_smart_contract(1, 0, 10);
+            assert_eq!(
+                result.iter().map(|a| a.id).collect::<Vec<u32>>(),
+                vec![0, 2]
+            );
+        }
+
+        #[ink::test]
+        fn test_attestations_show() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when attestation does not exist
+            // = * it raises an error
+            assert_eq!(
+                az_smart_contract_hub.attestations_show(0),
+                Err(AZSmartContractHubError::NotFound("Attestation".to_string()))
+            );
+            // = when attestation exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            az_smart_contract_hub
+                .auditors_create(accounts.bob, "Auditor".to_string())
+                .unwrap();
+            let attestation: Attestation = create_attestation(&mut az_smart_contract_hub, 0);
+            // = * it returns the attestation
+            assert_eq!(az_smart_contract_hub.attestations_show(0), Ok(attestation));
+        }
+
+        #[ink::test]
+        fn test_auditors_show() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when auditor does not exist
+            // = * it raises an error
+            assert_eq!(
+                az_smart_contract_hub.auditors_show(accounts.charlie),
+                Err(AZSmartContractHubError::NotFound("Auditor".to_string()))
+            );
+            // = when auditor exists
+            let auditor: Auditor = az_smart_contract_hub
+                .auditors_create(accounts.charlie, "Auditor".to_string())
+                .unwrap();
+            // = * it returns the auditor
+            assert_eq!(
+                az_smart_contract_hub.auditors_show(accounts.charlie),
+                Ok(auditor)
+            );
+        }
+
+        #[ink::test]
+        fn test_azero_id_status() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when smart contract does not exist
+            // * it raises an error
+            let result = az_smart_contract_hub.azero_id_status(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // when smart contract exists
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(5);
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // = when smart contract has not been revalidated
+            // = * it returns the status from when the AZERO.ID was last validated
+            let mut azero_id_status: AzeroIdStatus =
+                az_smart_contract_hub.azero_id_status(0).unwrap();
+            assert_eq!(azero_id_status.azero_id, MOCK_AZERO_ID.to_string());
+            assert!(!azero_id_status.stale);
+            assert_eq!(azero_id_status.checked_at, 5);
+            // = when smart contract has been revalidated
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.charlie));
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(6);
+            az_smart_contract_hub.revalidate(0).unwrap();
+            // = * it returns the revalidated status
+            azero_id_status = az_smart_contract_hub.azero_id_status(0).unwrap();
+            assert!(azero_id_status.stale);
+            assert_eq!(azero_id_status.checked_at, 6);
+        }
+
+        #[ink::test]
+        fn test_canonical_for() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when no smart contract has been made canonical
+            // * it raises an error
+            let mut result = az_smart_contract_hub.canonical_for(0, accounts.alice);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // when a smart contract has been made canonical
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            az_smart_contract_hub
+                .update_canonical(0, accounts.alice, Some(1))
+                .unwrap();
+            // * it returns the canonical smart contract
+            result = az_smart_contract_hub.canonical_for(0, accounts.alice);
+            assert_eq!(result.unwrap().id, 1);
+            // * it is scoped to the chain
+            result = az_smart_contract_hub.canonical_for(1, accounts.alice);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // when the canonical smart contract is disabled
+            az_smart_contract_hub
+                .update(
+                    1,
+                    false,
+                    MOCK_AZERO_ID.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            // * it raises an error
+            result = az_smart_contract_hub.canonical_for(0, accounts.alice);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            az_smart_contract_hub
+                .update(
+                    1,
+                    true,
+                    MOCK_AZERO_ID.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            // when the canonical smart contract is hidden
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub.update_hidden(1, true).unwrap();
+            // * it raises an error
+            result = az_smart_contract_hub.canonical_for(0, accounts.alice);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+        }
+
+        #[ink::test]
+        fn test_config() {
+            let (accounts, az_smart_contract_hub) = init();
+            let config = az_smart_contract_hub.config();
+            // * it returns the config
+            assert_eq!(config.azero_id_router_address, accounts.eve);
+            assert_eq!(config.az_groups_address, accounts.frank);
+            assert_eq!(config.chain, 0);
+            assert!(!config.import_open);
+            assert_eq!(config.smart_contracts_count, 0);
+            assert_eq!(config.storage_deposit, 0);
+            assert_eq!(config.storage_deposit_per_byte, 0);
+        }
+
+        #[ink::test]
+        fn test_deposit_of() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when smart contract does not exist
+            // * it raises an error
+            let result = az_smart_contract_hub.deposit_of(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // when smart contract exists
+            // = when no deposit was collected
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // = * it returns zero
+            assert_eq!(az_smart_contract_hub.deposit_of(0).unwrap(), 0);
+            // = when a deposit was collected
+            az_smart_contract_hub.update_storage_deposit(5).unwrap();
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // = * it returns the deposit
+            assert_eq!(az_smart_contract_hub.deposit_of(1).unwrap(), 5);
+        }
+
+        #[ink::test]
+        fn test_flags_show() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // = when flag does not exist
+            // = * it raises an error
+            assert_eq!(
+                az_smart_contract_hub.flags_show(0, accounts.bob),
+                Err(AZSmartContractHubError::NotFound("Flag".to_string()))
+            );
+            // = when flag exists
+            let flag: Flag = az_smart_contract_hub
+                .flags_create(0, FlagReason::Spam, None)
+                .unwrap();
+            // = * it returns the flag
+            assert_eq!(az_smart_contract_hub.flags_show(0, accounts.bob), Ok(flag));
+        }
+
+        #[ink::test]
+        fn test_has_role() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when account has the role
+            // = * it returns true
+            assert!(az_smart_contract_hub.has_role(HubRole::Admin, accounts.bob));
+            // = when account does not have the role
+            // = * it returns false
+            assert!(!az_smart_contract_hub.has_role(HubRole::Moderator, accounts.bob));
+            assert!(!az_smart_contract_hub.has_role(HubRole::Admin, accounts.charlie));
+            az_smart_contract_hub
+                .grant_role(HubRole::Moderator, accounts.charlie)
+                .unwrap();
+            assert!(az_smart_contract_hub.has_role(HubRole::Moderator, accounts.charlie));
+        }
+
+        #[ink::test]
+        fn test_interfaces_index() {
+            let (_accounts, mut az_smart_contract_hub) = init();
+            // = when there are no interfaces
+            // = * it returns an empty vector
+            assert_eq!(az_smart_contract_hub.interfaces_index(), vec![]);
+            // = when there are interfaces
+            let psp22: Interface = az_smart_contract_hub
+                .interfaces_create("PSP22".to_string(), Some([0x16, 0x2d, 0xf8, 0xc2]))
+                .unwrap();
+            let psp34: Interface = az_smart_contract_hub
+                .interfaces_create("PSP34".to_string(), None)
+                .unwrap();
+            // = * it returns all interfaces
+            assert_eq!(az_smart_contract_hub.interfaces_index(), vec![psp22, psp34]);
+        }
+
+        #[ink::test]
+        fn test_interfaces_show() {
+            let (_accounts, mut az_smart_contract_hub) = init();
+            // = when interface does not exist
+            // = * it raises an error
+            assert_eq!(
+                az_smart_contract_hub.interfaces_show(0),
+                Err(AZSmartContractHubError::NotFound("Interface".to_string()))
+            );
+            // = when interface exists
+            let interface: Interface = az_smart_contract_hub
+                .interfaces_create("PSP22".to_string(), None)
+                .unwrap();
+            // = * it returns the interface
+            assert_eq!(az_smart_contract_hub.interfaces_show(0), Ok(interface));
+        }
+
+        #[ink::test]
+        fn test_most_endorsed() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when there are no smart contracts for the chain and address
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.most_endorsed(0, accounts.alice);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when there are smart contracts for the chain and address
+            for _ in 0..3 {
+                create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            }
+            create_smart_contract(&mut az_smart_contract_hub, accounts.django);
+            // == when endorsements are tied
+            // == * it returns the oldest smart contract
+            result = az_smart_contract_hub.most_endorsed(0, accounts.alice);
+            assert_eq!(result.unwrap().id, 0);
+            // == when a smart contract has the most endorsements
+            for account in [accounts.charlie, accounts.django] {
+                set_caller::<DefaultEnvironment>(account);
+                az_smart_contract_hub.endorse(2, None).unwrap();
+            }
+            az_smart_contract_hub.endorse(1, None).unwrap();
+            az_smart_contract_hub.endorse(3, None).unwrap();
+            az_smart_contract_hub.endorse(3, None).unwrap_err();
+            // == * it returns that smart contract
+            result = az_smart_contract_hub.most_endorsed(0, accounts.alice);
+            assert_eq!(result.unwrap().id, 2);
+            // == when the most endorsed smart contract is hidden or disabled
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub.update_hidden(2, true).unwrap();
+            // == * it is ignored
+            result = az_smart_contract_hub.most_endorsed(0, accounts.alice);
+            assert_eq!(result.unwrap().id, 1);
+            az_smart_contract_hub
+                .update(
+                    1,
+                    false,
+                    MOCK_AZERO_ID.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            result = az_smart_contract_hub.most_endorsed(0, accounts.alice);
+            assert_eq!(result.unwrap().id, 0);
+            // = when the chain is different
+            // = * it raises an error
+            result = az_smart_contract_hub.most_endorsed(1, accounts.alice);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+        }
+
+        #[ink::test]
+        fn test_show() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when smart_contract does not exist
+            // * it returns error
+            assert_eq!(
+                az_smart_contract_hub.show(0),
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when smart_contract exists
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(
+                az_smart_contract_hub.fee,
+            );
+            let smart_contract: SmartContract = az_smart_contract_hub
+                .create(
+                    accounts.alice,
+                    0,
+                    Some(MOCK_AZERO_ID.to_string()),
+                    MOCK_ABI_URL.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            // = * it returns the smart_contract
+            assert_eq!(
+                az_smart_contract_hub.show(smart_contract.id),
+                Ok(smart_contract)
+            );
+        }
+
+        #[ink::test]
+        fn test_smart_contracts_by_interface() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when no smart contracts declare the interface
+            // = * it returns an empty vector
+            assert_eq!(
+                az_smart_contract_hub.smart_contracts_by_interface(0, 0, 10),
+                vec![]
+            );
+            // = when smart contracts declare the interface
+            az_smart_contract_hub
+                .interfaces_create("PSP22".to_string(), None)
+                .unwrap();
+            for _ in 0..3 {
+                let smart_contract: SmartContract =
+                    create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+                az_smart_contract_hub
+                    .update_interfaces(smart_contract.id, vec![0])
+                    .unwrap();
+            }
+            // = * it returns the requested page
+            let result: Vec<SmartContract> =
+                az_smart_contract_hub.smart_contracts_by_interface(0, 0, 2);
+            assert_eq!(
+                result.iter().map(|sc| sc.id).collect::<Vec<u32>>(),
+                vec![0, 1]
+            );
+            let result: Vec<SmartContract> =
+                az_smart_contract_hub.smart_contracts_by_interface(0, 1, 2);
+            assert_eq!(result.iter().map(|sc| sc.id).collect::<Vec<u32>>(), vec![2]);
+        }
+
+        #[ink::test]
+        fn test_smart_contracts_by_tag() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when no smart contracts have the tag
+            // = * it returns an empty vector
+            assert_eq!(
+                az_smart_contract_hub.smart_contracts_by_tag("dex".to_string(), 0, 10),
+                vec![]
+            );
+            // = when smart contracts have the tag
+            for _ in 0..3 {
+                let smart_contract: SmartContract =
+                    create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+                az_smart_contract_hub
+                    .update_tags(smart_contract.id, vec!["DEX".to_string()])
+                    .unwrap();
+            }
+            // = * it returns the requested page (case insensitive)
+            let result: Vec<SmartContract> =
+                az_smart_contract_hub.smart_contracts_by_tag(" Dex ".to_string(), 0, 2);
+            assert_eq!(
+                result.iter().map(|sc| sc.id).collect::<Vec<u32>>(),
+                vec![0, 1]
+            );
+            let result: Vec<SmartContract> =
+                az_smart_contract_hub.smart_contracts_by_tag("dex".to_string(), 1, 2);
+            assert_eq!(result.iter().map(|sc| sc.id).collect::<Vec<u32>>(), vec![2]);
+            // = when page is out of range
+            // = * it returns an empty vector
+            assert_eq!(
+                az_smart_contract_hub.smart_contracts_by_tag("dex".to_string(), 2, 2),
+                vec![]
+            );
+            // = when a smart contract with the tag is hidden
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub.update_hidden(1, true).unwrap();
+            // = * it is skipped
+            let result: Vec<SmartContract> =
+                az_smart_contract_hub.smart_contracts_by_tag("dex".to_string(), 0, 2);
+            assert_eq!(
+                result.iter().map(|sc| sc.id).collect::<Vec<u32>>(),
+                vec![0, 2]
+            );
+        }
+
+        // === TEST HANDLES ===
+        #[ink::test]
+        fn test_attestations_create() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // = when caller is not an auditor
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.attestations_create(
+                0,
+                MOCK_AUDIT_URL.to_string(),
+                Hash::from([1; 32]),
+                Hash::from([2; 32]),
+                Verdict::Passed,
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound("Auditor".to_string()))
+            );
+            // = when caller is an auditor
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub
+                .auditors_create(accounts.charlie, "Auditor".to_string())
+                .unwrap();
+            // == when auditor is disabled
+            az_smart_contract_hub
+                .auditors_update(accounts.charlie, "Auditor".to_string(), false)
+                .unwrap();
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // == * it raises an error
+            result = az_smart_contract_hub.attestations_create(
+                0,
+                MOCK_AUDIT_URL.to_string(),
+                Hash::from([1; 32]),
+                Hash::from([2; 32]),
+                Verdict::Passed,
+            );
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // == when auditor is enabled
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub
+                .auditors_update(accounts.charlie, "Auditor".to_string(), true)
+                .unwrap();
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // === when smart contract does not exist
+            // === * it raises an error
+            result = az_smart_contract_hub.attestations_create(
+                1,
+                MOCK_AUDIT_URL.to_string(),
+                Hash::from([1; 32]),
+                Hash::from([2; 32]),
+                Verdict::Passed,
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // === when smart contract exists
+            // ==== when report url is blank
+            // ==== * it raises an error
+            result = az_smart_contract_hub.attestations_create(
+                0,
+                " ".to_string(),
+                Hash::from([1; 32]),
+                Hash::from([2; 32]),
+                Verdict::Passed,
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Link to report can't be blank".to_string()
+                ))
+            );
+            // ==== when report url is present
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(123);
+            result = az_smart_contract_hub.attestations_create(
+                0,
+                MOCK_AUDIT_URL.to_string(),
+                Hash::from([1; 32]),
+                Hash::from([2; 32]),
+                Verdict::PassedWithIssues,
+            );
+            // ==== * it creates the attestation
+            assert_eq!(
+                result.unwrap(),
+                Attestation {
+                    id: 0,
+                    smart_contract_id: 0,
+                    auditor: accounts.charlie,
+                    report_url: MOCK_AUDIT_URL.to_string(),
+                    report_hash: Hash::from([1; 32]),
+                    scope_code_hash: Hash::from([2; 32]),
+                    verdict: Verdict::PassedWithIssues,
+                    timestamp: 123
+                }
+            );
+            // ==== * it increases the attestations count by one
+            assert_eq!(az_smart_contract_hub.attestations_count, 1);
+            // ==== * it indexes the attestation by smart contract and auditor
+            assert_eq!(
+                az_smart_contract_hub
+                    .attestation_ids_by_smart_contract
+                    .get((0, 0)),
+                Some(0)
+            );
+            assert_eq!(
+                az_smart_contract_hub
+                    .attestations_count_by_smart_contract
+                    .get(0),
+                Some(1)
+            );
+            assert_eq!(
+                az_smart_contract_hub
+                    .attestation_ids_by_auditor
+                    .get((accounts.charlie, 0)),
+                Some(0)
+            );
+            assert_eq!(
+                az_smart_contract_hub
+                    .attestations_count_by_auditor
+                    .get(accounts.charlie),
+                Some(1)
+            );
+            // ==== when attestations_count is u32::MAX
+            az_smart_contract_hub.attestations_count = u32::MAX;
+            // ==== * it raises an error
+            result = az_smart_contract_hub.attestations_create(
+                0,
+                MOCK_AUDIT_URL.to_string(),
+                Hash::from([1; 32]),
+                Hash::from([2; 32]),
+                Verdict::Failed,
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Attestation limit reached".to_string()
+                ))
+            );
+        }
+
+        #[ink::test]
+        fn test_auditors_create() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result =
+                az_smart_contract_hub.auditors_create(accounts.charlie, "Auditor".to_string());
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when name is blank
+            // == * it raises an error
+            result = az_smart_contract_hub.auditors_create(accounts.charlie, " ".to_string());
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Name can't be blank".to_string()
+                ))
+            );
+            // == when name is present
+            // == * it creates an enabled auditor
+            result =
+                az_smart_contract_hub.auditors_create(accounts.charlie, " Auditor ".to_string());
+            assert_eq!(
+                result.unwrap(),
+                Auditor {
+                    account: accounts.charlie,
+                    name: "Auditor".to_string(),
+                    enabled: true
+                }
+            );
+            // == when auditor already exists
+            // == * it raises an error
+            result = az_smart_contract_hub.auditors_create(accounts.charlie, "Auditor".to_string());
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Auditor has already been taken".to_string()
+                ))
+            );
+        }
+
+        #[ink::test]
+        fn test_auditors_update() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.auditors_update(
+                accounts.charlie,
+                "Auditor".to_string(),
+                false,
+            );
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when auditor does not exist
+            // == * it raises an error
+            result = az_smart_contract_hub.auditors_update(
+                accounts.charlie,
+                "Auditor".to_string(),
+                false,
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound("Auditor".to_string()))
+            );
+            // == when auditor exists
+            az_smart_contract_hub
+                .auditors_create(accounts.charlie, "Auditor".to_string())
+                .unwrap();
+            // === when name is blank
+            // === * it raises an error
+            result =
+                az_smart_contract_hub.auditors_update(accounts.charlie, " ".to_string(), false);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Name can't be blank".to_string()
+                ))
+            );
+            // === when name is present
+            // === * it updates the auditor
+            result = az_smart_contract_hub.auditors_update(
+                accounts.charlie,
+                "Audit Co".to_string(),
+                false,
+            );
+            assert_eq!(
+                result.unwrap(),
+                Auditor {
+                    account: accounts.charlie,
+                    name: "Audit Co".to_string(),
+                    enabled: false
+                }
+            );
+        }
+
+        #[ink::test]
+        fn test_claim_canonical() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when smart contract does not exist
+            // * it raises an error
+            let mut result = az_smart_contract_hub.claim_canonical(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // when smart contract exists
+            let mut smart_contract: SmartContract =
+                create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // = when called by non-owner
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // = * it raises an error
+            result = az_smart_contract_hub.claim_canonical(0);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by owner
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when smart contract is on a different chain
+            smart_contract.chain = 1;
+            az_smart_contract_hub
+                .smart_contracts
+                .insert(smart_contract.id, &smart_contract);
+            // == * it raises an error
+            result = az_smart_contract_hub.claim_canonical(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contract is on a different chain".to_string()
+                ))
+            );
+            // == when smart contract is on the same chain
+            // == * it calls the smart contract to verify the owner (Tested in e2e tests)
+        }
+
+        #[ink::test]
+        fn test_clear_flags() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.clear_flags(0);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when smart contract does not exist
+            // == * it raises an error
+            result = az_smart_contract_hub.clear_flags(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // == when smart contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            for account in [accounts.charlie, accounts.django] {
+                set_caller::<DefaultEnvironment>(account);
+                az_smart_contract_hub
+                    .flags_create(0, FlagReason::Phishing, None)
+                    .unwrap();
+            }
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == * it resets the flags count
+            result = az_smart_contract_hub.clear_flags(0);
+            assert_eq!(result.unwrap().flags_count, 0);
+            // == * it removes the flags
+            assert_eq!(
+                az_smart_contract_hub.flags_show(0, accounts.charlie),
+                Err(AZSmartContractHubError::NotFound("Flag".to_string()))
+            );
+            assert_eq!(
+                az_smart_contract_hub.flags_show(0, accounts.django),
+                Err(AZSmartContractHubError::NotFound("Flag".to_string()))
+            );
+            // == * it allows the smart contract to be flagged again
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            az_smart_contract_hub
+                .flags_create(0, FlagReason::Phishing, None)
+                .unwrap();
+        }
+
+        #[ink::test]
+        fn test_create() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when the import is open
+            az_smart_contract_hub.import_open = true;
+            // * it raises an error
+            assert_eq!(
+                az_smart_contract_hub.create(
+                    accounts.alice,
+                    0,
+                    Some(MOCK_AZERO_ID.to_string()),
+                    MOCK_ABI_URL.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                ),
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Import is in progress".to_string(),
+                ))
+            );
+            az_smart_contract_hub.import_open = false;
+            // when smart_contracts_count is u32::MAX
+            az_smart_contract_hub.smart_contracts_count = u32::MAX;
+            // * it raises an error
+            let result = az_smart_contract_hub.create(
+                accounts.alice,
+                0,
+                Some(MOCK_AZERO_ID.to_string()),
+                MOCK_ABI_URL.to_string(),
+                Some(MOCK_CONTRACT_URL.to_string()),
+                Some(MOCK_WASM_URL.to_string()),
+                Some(MOCK_AUDIT_URL.to_string()),
+                Some(5),
+                Some(MOCK_PROJECT_NAME.to_string()),
+                Some(MOCK_PROJECT_WEBSITE.to_string()),
+                Some(MOCK_GITHUB.to_string()),
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contract limit reached".to_string(),
+                ))
+            );
+            // when smart_contracts_count is less than u32::MAX
+            az_smart_contract_hub.smart_contracts_count = 0;
+            ink::env::test::advance_block::<DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(5);
+            let smart_contract: SmartContract =
+                create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // * it records the block timestamp and block number
+            assert_eq!(smart_contract.created_at, 5);
+            assert_eq!(smart_contract.created_at_block, 1);
+            assert_eq!(smart_contract.updated_at, 5);
+            assert_eq!(smart_contract.updated_at_block, 1);
+            // = when azero_id is not provided
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(az_smart_contract_hub.fee);
+            let result = az_smart_contract_hub.create(
+                accounts.alice,
+                0,
+                None,
+                MOCK_ABI_URL.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            // = * it uses the caller's primary AZERO.ID
+            assert_eq!(result.unwrap().azero_id, MOCK_AZERO_ID.to_string());
+            // = when azero_id is not provided and the caller has no primary AZERO.ID
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            let result = az_smart_contract_hub.create(
+                accounts.alice,
+                0,
+                None,
+                MOCK_ABI_URL.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            // = * it raises an error
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "PrimaryDomain".to_string()
+                ))
+            );
+            // = when azero_id does not exist
+            let result = az_smart_contract_hub.create(
+                accounts.alice,
+                0,
+                Some("absent.azero".to_string()),
+                MOCK_ABI_URL.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            // = * it raises the router's error
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::AzeroIdRouterError(
+                    azero_id_router::in_memory::DOMAIN_NOT_FOUND
+                ))
+            );
+            // * the rest is tested below
+        }
+
+        #[ink::test]
+        fn test_destroy() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when smart contract does not exist
+            // * it raises an error
+            let mut result = az_smart_contract_hub.destroy(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // when smart contract exists
+            az_smart_contract_hub.update_storage_deposit(5).unwrap();
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            az_smart_contract_hub
+                .update_tags(0, vec!["DeFi".to_string()])
+                .unwrap();
+            az_smart_contract_hub
+                .interfaces_create("PSP22".to_string(), None)
+                .unwrap();
+            az_smart_contract_hub.update_interfaces(0, vec![0]).unwrap();
+            az_smart_contract_hub
+                .update_canonical(0, accounts.alice, Some(0))
+                .unwrap();
+            az_smart_contract_hub
+                .auditors_create(accounts.bob, "Auditor".to_string())
+                .unwrap();
+            create_attestation(&mut az_smart_contract_hub, 0);
+            create_attestation(&mut az_smart_contract_hub, 1);
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            az_smart_contract_hub.endorse(0, None).unwrap();
+            az_smart_contract_hub
+                .flags_create(0, FlagReason::Spam, None)
+                .unwrap();
+            // = when called by non-owner
+            // = * it raises an error
+            result = az_smart_contract_hub.destroy(0);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by owner
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            let balance_before: Balance =
+                ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap();
+            result = az_smart_contract_hub.destroy(0);
+            // = * it refunds the storage deposit
+            assert_eq!(result.unwrap(), 5);
+            assert_eq!(
+                ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.bob).unwrap(),
+                balance_before + 5
+            );
+            assert!(az_smart_contract_hub.deposits.get(0).is_none());
+            // = * it removes the smart contract
+            assert!(az_smart_contract_hub.smart_contracts.get(0).is_none());
+            // = * it removes the smart contract from the indexes
+            assert_eq!(
+                smart_contract_ids_by_address(&az_smart_contract_hub, accounts.alice),
+                vec![1]
+            );
+            assert!(az_smart_contract_hub
+                .canonical_ids
+                .get((0, accounts.alice))
+                .is_none());
+            assert!(smart_contract_ids_by_tag(&az_smart_contract_hub, "defi").is_empty());
+            assert!(smart_contract_ids_by_interface(&az_smart_contract_hub, 0).is_empty());
+            assert!(az_smart_contract_hub
+                .attestations_by_smart_contract(0, 0, 10)
+                .is_empty());
+            // = * it removes its attestations
+            assert_eq!(
+                az_smart_contract_hub.attestations_show(0),
+                Err(AZSmartContractHubError::NotFound("Attestation".to_string()))
+            );
+            assert_eq!(
+                az_smart_contract_hub
+                    .attestations_by_auditor(accounts.bob, 0, 10)
+                    .iter()
+                    .map(|a| a.id)
+                    .collect::<Vec<u32>>(),
+                vec![1]
+            );
+            // = * it leaves the endorsements and flags unreachable
+            assert_eq!(
+                az_smart_contract_hub.flags_show(0, accounts.charlie),
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = * it does not reuse the id
+            assert_eq!(
+                create_smart_contract(&mut az_smart_contract_hub, accounts.alice).id,
+                2
+            );
+        }
+
+        #[ink::test]
+        fn test_endorse() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when smart contract does not exist
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.endorse(0, None);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when smart contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // == when endorsements require an AZERO.ID
+            az_smart_contract_hub.endorsements_require_azero_id = true;
+            // === when AZERO.ID isn't provided
+            // === * it raises an error
+            result = az_smart_contract_hub.endorse(0, None);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "AZERO.ID can't be blank".to_string()
+                ))
+            );
+            // === when AZERO.ID is provided
+            // === * it endorses the smart contract
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.charlie));
+            result = az_smart_contract_hub.endorse(0, Some(MOCK_AZERO_ID.to_string()));
+            assert_eq!(result.unwrap().endorsements_count, 1);
+            // == when endorsements don't require an AZERO.ID
+            az_smart_contract_hub.endorsements_require_azero_id = false;
+            // === when caller has already endorsed the smart contract
+            // === * it raises an error
+            result = az_smart_contract_hub.endorse(0, None);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Endorsement has already been taken".to_string()
+                ))
+            );
+            // === when caller hasn't endorsed the smart contract
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // === * it increases the endorsements count by one
+            result = az_smart_contract_hub.endorse(0, None);
+            assert_eq!(result.unwrap().endorsements_count, 2);
+            // === * it stores the endorsement
+            assert!(az_smart_contract_hub
+                .endorsements
+                .contains((0, accounts.django)));
+        }
+
+        #[ink::test]
+        fn test_flags_create() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when smart contract does not exist
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.flags_create(0, FlagReason::Spam, None);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when smart contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // == when text is too long
+            // == * it raises an error
+            result = az_smart_contract_hub.flags_create(
+                0,
+                FlagReason::Other,
+                Some("a".repeat(FLAG_TEXT_LENGTH_LIMIT + 1)),
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                    "Text can't be longer than {FLAG_TEXT_LENGTH_LIMIT} bytes"
+                )))
+            );
+            // == when text is blank
+            // == * it stores no text
+            result = az_smart_contract_hub.flags_create(0, FlagReason::Spam, Some(" ".to_string()));
+            assert_eq!(
+                result.unwrap(),
+                Flag {
+                    reason: FlagReason::Spam,
+                    text: None
+                }
+            );
+            // == * it increases the smart contract's flags count by one
+            assert_eq!(az_smart_contract_hub.show(0).unwrap().flags_count, 1);
+            // == when caller has already flagged the smart contract
+            // == * it raises an error
+            result = az_smart_contract_hub.flags_create(0, FlagReason::Spam, None);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Flag has already been taken".to_string()
+                ))
+            );
+            // == when text is present
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // == * it stores the trimmed text
+            result = az_smart_contract_hub.flags_create(
+                0,
+                FlagReason::Phishing,
+                Some(" Website asks for seed phrase ".to_string()),
+            );
+            assert_eq!(
+                result.unwrap(),
+                Flag {
+                    reason: FlagReason::Phishing,
+                    text: Some("Website asks for seed phrase".to_string())
+                }
+            );
+            assert_eq!(az_smart_contract_hub.show(0).unwrap().flags_count, 2);
+        }
+
+        #[ink::test]
+        fn test_grant_role() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            az_smart_contract_hub
+                .grant_role(HubRole::Moderator, accounts.django)
+                .unwrap();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.grant_role(HubRole::Moderator, accounts.charlie);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when account already has the role
+            // == * it raises an error
+            result = az_smart_contract_hub.grant_role(HubRole::Moderator, accounts.django);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Role has already been granted".to_string()
+                ))
+            );
+            // == when account does not have the role
+            // == * it grants the role
+            az_smart_contract_hub
+                .grant_role(HubRole::Admin, accounts.charlie)
+                .unwrap();
+            assert!(az_smart_contract_hub.has_role(HubRole::Admin, accounts.charlie));
+        }
+
+        #[ink::test]
+        fn test_import() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            let smart_contract_v0 = |id: u32| SmartContractV0 {
+                id,
+                smart_contract_address: accounts.alice,
+                chain: 0,
+                caller: accounts.charlie,
+                enabled: false,
+                azero_id: MOCK_AZERO_ID.to_string(),
+                abi_url: MOCK_ABI_URL.to_string(),
+                contract_url: None,
+                wasm_url: None,
+                audit_url: None,
+                group_id: None,
+                project_name: Some(MOCK_PROJECT_NAME.to_string()),
+                project_website: None,
+                github: None,
+            };
+            az_smart_contract_hub.import_open = true;
+            // when called by non-admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // * it raises an error
+            let mut result = az_smart_contract_hub.import(vec![smart_contract_v0(0)]);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // = when smart contracts are not in order
+            // = * it raises an error
+            result = az_smart_contract_hub.import(vec![smart_contract_v0(1)]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contracts must be imported in order".to_string()
+                ))
+            );
+            // = when smart contracts are in order
+            result = az_smart_contract_hub.import(vec![smart_contract_v0(0), smart_contract_v0(1)]);
+            // = * it returns the smart contracts count
+            assert_eq!(result.unwrap(), 2);
+            // = * it stores the smart contracts with their original ids and callers
+            let smart_contract: SmartContract = az_smart_contract_hub.show(1).unwrap();
+            assert_eq!(smart_contract.caller, accounts.charlie);
+            assert!(!smart_contract.enabled);
+            assert_eq!(
+                smart_contract.project_name,
+                Some(MOCK_PROJECT_NAME.to_string())
+            );
+            // = * it sets the timestamps and block numbers to zero
+            assert_eq!(smart_contract.created_at, 0);
+            assert_eq!(smart_contract.updated_at_block, 0);
+            // = * it adds the smart contracts to the address index
+            assert_eq!(
+                smart_contract_ids_by_address(&az_smart_contract_hub, accounts.alice),
+                vec![0, 1]
+            );
+            // = when the import has been finalised
+            az_smart_contract_hub.finalise_import().unwrap();
+            // = * it raises an error
+            result = az_smart_contract_hub.import(vec![smart_contract_v0(2)]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Import has been finalised".to_string()
+                ))
+            );
+            // = * new smart contracts are created after the imported ones
+            assert_eq!(
+                create_smart_contract(&mut az_smart_contract_hub, accounts.alice).id,
+                2
+            );
+        }
+
+        #[ink::test]
+        fn test_finalise_import() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            az_smart_contract_hub.import_open = true;
+            // when called by non-admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // * it raises an error
+            let mut result = az_smart_contract_hub.finalise_import();
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // * it closes the import
+            result = az_smart_contract_hub.finalise_import();
+            assert!(result.is_ok());
+            assert!(!az_smart_contract_hub.config().import_open);
+        }
+
+        #[ink::test]
+        fn test_interfaces_create() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.interfaces_create("PSP22".to_string(), None);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when name is blank
+            // == * it raises an error
+            result = az_smart_contract_hub.interfaces_create(" ".to_string(), None);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Name can't be blank".to_string()
+                ))
+            );
+            // == when name is present
+            result = az_smart_contract_hub
+                .interfaces_create(" PSP22 ".to_string(), Some([0x16, 0x2d, 0xf8, 0xc2]));
+            // == * it creates the interface with a trimmed name
+            assert_eq!(
+                result.unwrap(),
+                Interface {
+                    id: 0,
+                    name: "PSP22".to_string(),
+                    selector: Some([0x16, 0x2d, 0xf8, 0xc2]),
+                    selector_version: 0,
+                    enabled: true
+                }
+            );
+            // == * it increases the interfaces count by one
+            assert_eq!(az_smart_contract_hub.interfaces_count, 1);
+            // == when interfaces_count is u32::MAX
+            az_smart_contract_hub.interfaces_count = u32::MAX;
+            // == * it raises an error
+            result = az_smart_contract_hub.interfaces_create("PSP34".to_string(), None);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Interface limit reached".to_string()
+                ))
+            );
+        }
+
+        #[ink::test]
+        fn test_interfaces_update() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result =
+                az_smart_contract_hub.interfaces_update(0, "PSP22".to_string(), None, false);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when interface does not exist
+            // == * it raises an error
+            result = az_smart_contract_hub.interfaces_update(0, "PSP22".to_string(), None, false);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound("Interface".to_string()))
+            );
+            // == when interface exists
+            az_smart_contract_hub
+                .interfaces_create("PSP22".to_string(), None)
+                .unwrap();
+            // === when name is blank
+            // === * it raises an error
+            result = az_smart_contract_hub.interfaces_update(0, " ".to_string(), None, false);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Name can't be blank".to_string()
+                ))
+            );
+            // === when name is present
+            // === * it updates the interface
+            result = az_smart_contract_hub.interfaces_update(
+                0,
+                "PSP34".to_string(),
+                Some([0xff, 0xa0, 0xde, 0x18]),
+                false,
+            );
+            assert_eq!(
+                result.unwrap(),
+                Interface {
+                    id: 0,
+                    name: "PSP34".to_string(),
+                    selector: Some([0xff, 0xa0, 0xde, 0x18]),
+                    selector_version: 1,
+                    enabled: false
+                }
+            );
+            // === when interface has verified declarations
+            az_smart_contract_hub
+                .interfaces_update(0, "PSP34".to_string(), Some([0xff, 0xa0, 0xde, 0x18]), true)
+                .unwrap();
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            az_smart_contract_hub.update_interfaces(0, vec![0]).unwrap();
+            let mut smart_contract: SmartContract = az_smart_contract_hub.show(0).unwrap();
+            smart_contract.interfaces[0].verified = true;
+            az_smart_contract_hub
+                .smart_contracts
+                .insert(0, &smart_contract);
+            // ==== when selector is unchanged
+            az_smart_contract_hub
+                .interfaces_update(0, "PSP34".to_string(), Some([0xff, 0xa0, 0xde, 0x18]), true)
+                .unwrap();
+            // ==== * it keeps the declarations verified
+            assert!(az_smart_contract_hub.show(0).unwrap().interfaces[0].verified);
+            // ==== when selector changes
+            result = az_smart_contract_hub.interfaces_update(
+                0,
+                "PSP34".to_string(),
+                Some([0x16, 0x2d, 0xf8, 0xc2]),
+                true,
+            );
+            // ==== * it increases the selector version
+            assert_eq!(result.unwrap().selector_version, 2);
+            // ==== * it marks the declarations as unverified
+            assert!(!az_smart_contract_hub.show(0).unwrap().interfaces[0].verified);
+            assert!(
+                !az_smart_contract_hub.smart_contracts_by_interface(0, 0, 1)[0].interfaces[0]
+                    .verified
+            );
+        }
+
+        #[ink::test]
+        fn test_revalidate() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when smart contract does not exist
+            // * it raises an error
+            let mut result = az_smart_contract_hub.revalidate(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // when smart contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // = when AZERO.ID no longer resolves to the smart contract's caller
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.charlie));
+            ink::env::test::advance_block::<DefaultEnvironment>();
+            // = * it marks the status as stale
+            result = az_smart_contract_hub.revalidate(0);
+            let azero_id_status: AzeroIdStatus = result.unwrap();
+            assert!(azero_id_status.stale);
+            assert_eq!(azero_id_status.checked_at_block, 1);
+            // = when AZERO.ID does not exist
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, None);
+            // = * it marks the status as stale
+            result = az_smart_contract_hub.revalidate(0);
+            assert!(result.unwrap().stale);
+            // = when AZERO.ID resolves to the smart contract's caller
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.bob));
+            // = * it marks the status as not stale
+            result = az_smart_contract_hub.revalidate(0);
+            assert!(!result.unwrap().stale);
+            // = when smart contract is updated
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.charlie));
+            az_smart_contract_hub.revalidate(0).unwrap();
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub
+                .update(
+                    0,
+                    true,
+                    MOCK_AZERO_ID_TWO.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            // = * it resets the status
+            assert!(az_smart_contract_hub.azero_id_statuses.get(0).is_none());
+            assert_eq!(
+                az_smart_contract_hub.azero_id_status(0).unwrap().azero_id,
+                MOCK_AZERO_ID_TWO.to_string()
+            );
+        }
+
+        #[ink::test]
+        fn test_revoke_role() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.revoke_role(HubRole::Admin, accounts.bob);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when revoking own admin role
+            // == * it raises an error
+            result = az_smart_contract_hub.revoke_role(HubRole::Admin, accounts.bob);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Can't revoke own admin role".to_string()
+                ))
+            );
+            // == when account does not have the role
+            // == * it raises an error
+            result = az_smart_contract_hub.revoke_role(HubRole::Moderator, accounts.django);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound("Role".to_string()))
+            );
+            // == when account has the role
+            az_smart_contract_hub
+                .grant_role(HubRole::Moderator, accounts.django)
+                .unwrap();
+            // == * it revokes the role
+            az_smart_contract_hub
+                .revoke_role(HubRole::Moderator, accounts.django)
+                .unwrap();
+            assert!(!az_smart_contract_hub.has_role(HubRole::Moderator, accounts.django));
+        }
+
+        #[ink::test]
+        fn test_unendorse() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when smart contract does not exist
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.unendorse(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when smart contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // == when caller hasn't endorsed the smart contract
+            // == * it raises an error
+            result = az_smart_contract_hub.unendorse(0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound("Endorsement".to_string()))
+            );
+            // == when caller has endorsed the smart contract
+            az_smart_contract_hub.endorse(0, None).unwrap();
+            // == * it decreases the endorsements count by one
+            result = az_smart_contract_hub.unendorse(0);
+            assert_eq!(result.unwrap().endorsements_count, 0);
+            // == * it removes the endorsement
+            assert!(!az_smart_contract_hub
+                .endorsements
+                .contains((0, accounts.bob)));
+        }
+
+        #[ink::test]
+        fn test_update() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when smart_contract doesn't exist
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.update(
+                0,
+                false,
+                MOCK_AZERO_ID.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+
+            // = when smart_contract exists
+            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(
+                az_smart_contract_hub.fee,
+            );
+            az_smart_contract_hub
+                .create(
+                    accounts.alice,
+                    0,
+                    Some(MOCK_AZERO_ID.to_string()),
+                    MOCK_ABI_URL.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+                .unwrap();
+            // == when called by account that is not the original caller
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // == * it raises an error
+            result = az_smart_contract_hub.update(
+                0,
+                false,
+                MOCK_AZERO_ID.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // == when called by account that is the original caller
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // === when group does not exist
+            // === * it raises an error
+            let update_group_id = |az_smart_contract_hub: &mut AZSmartContractHub| {
+                az_smart_contract_hub.update(
+                    0,
+                    true,
+                    MOCK_AZERO_ID.to_string(),
+                    Some(412),
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            };
+            result = update_group_id(&mut az_smart_contract_hub);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::AZGroupsError(
+                    AZGroupsError::NotFound("Group".to_string())
+                ))
+            );
+            // === when group is disabled
+            in_memory::set_group(412, false);
+            // === * it raises an error
+            result = update_group_id(&mut az_smart_contract_hub);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::AZGroupsError(
+                    AZGroupsError::GroupDisabled
+                ))
+            );
+            // === when group is enabled
+            in_memory::set_group(412, true);
+            // ==== when caller is not in the group
+            // ==== * it raises an error
+            result = update_group_id(&mut az_smart_contract_hub);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::AZGroupsError(
+                    AZGroupsError::NotFound("GroupUser".to_string())
+                ))
+            );
+            // ==== when caller is banned or an applicant
+            // ==== * it raises an error
+            for role in [Role::Banned, Role::Applicant] {
+                in_memory::set_group_user(412, accounts.bob, role);
+                result = update_group_id(&mut az_smart_contract_hub);
+                assert_eq!(
+                    result,
+                    Err(AZSmartContractHubError::AZGroupsError(
+                        AZGroupsError::NotAMember
+                    ))
+                );
+            }
+            // ==== when caller is a member
+            in_memory::set_group_user(412, accounts.bob, Role::Member);
+            // ===== when caller can't manage the group's hub records
+            // ===== * it raises an error
+            in_memory::set_role_permissions(412, Role::Member, vec![]);
+            result = update_group_id(&mut az_smart_contract_hub);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // ===== when caller can manage the group's hub records
+            in_memory::set_permissions(412, accounts.bob, vec![Permission::ManageHubRecords]);
+            ink::env::test::advance_block::<DefaultEnvironment>();
+            ink::env::test::set_block_timestamp::<DefaultEnvironment>(5);
+            result = az_smart_contract_hub.update(
+                0,
+                false,
+                MOCK_AZERO_ID_TWO.to_string(),
+                Some(412),
+                Some(MOCK_AUDIT_URL.to_string()),
+                Some(MOCK_PROJECT_NAME.to_string()),
+                Some(MOCK_PROJECT_WEBSITE.to_string()),
+                Some(MOCK_GITHUB.to_string()),
+            );
+            let result_unwrapped = result.unwrap();
+            // == * it updates the enabled status
+            assert_eq!(result_unwrapped.enabled, false);
+            // == * it updates the azero id
+            assert_eq!(result_unwrapped.azero_id, MOCK_AZERO_ID_TWO.to_string());
+            // == * it updates the group id
+            assert_eq!(result_unwrapped.group_id, Some(412));
+            // == * it updates the audit url
+            assert_eq!(result_unwrapped.audit_url, Some(MOCK_AUDIT_URL.to_string()));
+            // == * it updates the project name
+            assert_eq!(
+                result_unwrapped.project_name,
+                Some(MOCK_PROJECT_NAME.to_string())
+            );
+            // == * it updates the project website
+            assert_eq!(
+                result_unwrapped.project_website,
+                Some(MOCK_PROJECT_WEBSITE.to_string())
+            );
+            // == * it updates the github
+            assert_eq!(result_unwrapped.github, Some(MOCK_GITHUB.to_string()));
+            // == * it updates the block timestamp and block number
+            assert_eq!(result_unwrapped.created_at, 0);
+            assert_eq!(result_unwrapped.created_at_block, 0);
+            assert_eq!(result_unwrapped.updated_at, 5);
+            assert_eq!(result_unwrapped.updated_at_block, 1);
         }
 
-        // === TESTS ===
-        // === TEST QUERIES ===
         #[ink::test]
-        fn test_config() {
-            let (accounts, az_smart_contract_hub) = init();
-            let config = az_smart_contract_hub.config();
-            // * it returns the config
-            assert_eq!(config.azero_id_router_address, accounts.eve);
-            assert_eq!(config.az_groups_address, accounts.frank);
-            assert_eq!(config.smart_contracts_count, 0);
+        fn test_update_canonical() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when called by non-moderator
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // * it raises an error
+            let mut result = az_smart_contract_hub.update_canonical(0, accounts.alice, Some(0));
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // when called by moderator
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub
+                .grant_role(HubRole::Moderator, accounts.django)
+                .unwrap();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = when smart contract does not exist
+            // = * it raises an error
+            result = az_smart_contract_hub.update_canonical(0, accounts.alice, Some(0));
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when smart contract exists
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // == when smart contract does not match chain and address
+            // == * it raises an error
+            for (chain, smart_contract_address) in [(1, accounts.alice), (0, accounts.charlie)] {
+                result =
+                    az_smart_contract_hub.update_canonical(chain, smart_contract_address, Some(0));
+                assert_eq!(
+                    result,
+                    Err(AZSmartContractHubError::UnprocessableEntity(
+                        "Smart contract does not match chain and address".to_string()
+                    ))
+                );
+            }
+            // == when smart contract matches chain and address
+            // == * it sets the canonical smart contract
+            result = az_smart_contract_hub.update_canonical(0, accounts.alice, Some(0));
+            assert_eq!(result.unwrap(), Some(0));
+            assert_eq!(
+                az_smart_contract_hub.canonical_ids.get((0, accounts.alice)),
+                Some(0)
+            );
+            // = when id is None
+            // = * it removes the canonical smart contract
+            result = az_smart_contract_hub.update_canonical(0, accounts.alice, None);
+            assert_eq!(result.unwrap(), None);
+            assert_eq!(
+                az_smart_contract_hub.canonical_ids.get((0, accounts.alice)),
+                None
+            );
+        }
+
+        #[ink::test]
+        fn test_update_endorsements_require_azero_id() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let result = az_smart_contract_hub.update_endorsements_require_azero_id(true);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // = * it updates whether endorsements require an AZERO.ID
+            az_smart_contract_hub
+                .update_endorsements_require_azero_id(true)
+                .unwrap();
+            assert!(az_smart_contract_hub.endorsements_require_azero_id);
+        }
+
+        #[ink::test]
+        fn test_update_fee() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // * it raises an error
+            let result = az_smart_contract_hub.update_fee(5);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // when called by the admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // * it updates the fee
+            az_smart_contract_hub.update_fee(5).unwrap();
+            assert_eq!(az_smart_contract_hub.fee, 5);
+            // when called by a fee manager
+            az_smart_contract_hub
+                .grant_role(HubRole::FeeManager, accounts.django)
+                .unwrap();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // * it updates the fee
+            az_smart_contract_hub.update_fee(6).unwrap();
+            assert_eq!(az_smart_contract_hub.fee, 6)
+        }
+
+        #[ink::test]
+        fn test_update_hidden() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when called by non admin
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.update_hidden(0, true);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // = when called by admin
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // == when smart contract does not exist
+            // == * it raises an error
+            result = az_smart_contract_hub.update_hidden(0, true);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // == when smart contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // == * it updates the hidden status
+            result = az_smart_contract_hub.update_hidden(0, true);
+            let smart_contract: SmartContract = result.unwrap();
+            assert!(smart_contract.hidden);
+            // == * it does not change the enabled status
+            assert!(smart_contract.enabled);
+            // = when called by a moderator
+            az_smart_contract_hub
+                .grant_role(HubRole::Moderator, accounts.django)
+                .unwrap();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // = * it updates the hidden status
+            result = az_smart_contract_hub.update_hidden(0, false);
+            assert!(!result.unwrap().hidden);
+        }
+
+        #[ink::test]
+        fn test_update_interfaces() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // = when smart_contract doesn't exist
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.update_interfaces(0, vec![0]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when smart_contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            // == when called by account that is not the original caller
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            // == * it raises an error
+            result = az_smart_contract_hub.update_interfaces(0, vec![0]);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // == when called by account that is the original caller
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // === when interface does not exist
+            // === * it raises an error
+            result = az_smart_contract_hub.update_interfaces(0, vec![0]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound("Interface".to_string()))
+            );
+            // === when interface is disabled
+            for i in 0..=INTERFACES_LIMIT {
+                az_smart_contract_hub
+                    .interfaces_create(format!("PSP{i}"), None)
+                    .unwrap();
+            }
+            az_smart_contract_hub
+                .interfaces_update(0, "PSP0".to_string(), None, false)
+                .unwrap();
+            // === * it raises an error
+            result = az_smart_contract_hub.update_interfaces(0, vec![0]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Interface is disabled".to_string()
+                ))
+            );
+            az_smart_contract_hub
+                .interfaces_update(0, "PSP0".to_string(), None, true)
+                .unwrap();
+            // === when there are too many interfaces
+            // === * it raises an error
+            result =
+                az_smart_contract_hub.update_interfaces(0, (0..=INTERFACES_LIMIT as u32).collect());
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                    "Interfaces limit is {INTERFACES_LIMIT}"
+                )))
+            );
+            // === when interfaces are valid
+            result = az_smart_contract_hub.update_interfaces(0, vec![0, 1, 0]);
+            // === * it stores the deduplicated declarations as unverified
+            assert_eq!(
+                result.unwrap().interfaces,
+                vec![
+                    InterfaceDeclaration {
+                        interface_id: 0,
+                        verified: false,
+                        selector_version: 0
+                    },
+                    InterfaceDeclaration {
+                        interface_id: 1,
+                        verified: false,
+                        selector_version: 0
+                    }
+                ]
+            );
+            // === * it indexes the smart contract by interface
+            assert_eq!(
+                smart_contract_ids_by_interface(&az_smart_contract_hub, 0),
+                vec![0]
+            );
+            assert_eq!(
+                smart_contract_ids_by_interface(&az_smart_contract_hub, 1),
+                vec![0]
+            );
+            // === when an interface remains declared
+            let mut smart_contract: SmartContract = az_smart_contract_hub.show(0).unwrap();
+            smart_contract.interfaces[1].verified = true;
+            az_smart_contract_hub
+                .smart_contracts
+                .insert(0, &smart_contract);
+            // === * it keeps its verification status
+            // === when an interface is no longer declared
+            // === * it removes the smart contract from that interface's index
+            result = az_smart_contract_hub.update_interfaces(0, vec![1]);
+            assert_eq!(
+                result.unwrap().interfaces,
+                vec![InterfaceDeclaration {
+                    interface_id: 1,
+                    verified: true,
+                    selector_version: 0
+                }]
+            );
+            assert!(smart_contract_ids_by_interface(&az_smart_contract_hub, 0).is_empty());
+            assert!(az_smart_contract_hub
+                .smart_contracts_count_by_interface
+                .get(0)
+                .is_none());
+        }
+
+        #[ink::test]
+        fn test_update_storage_deposit() {
+            let (accounts, mut az_smart_contract_hub) = init();
+            // when called by non fee manager
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // * it raises an error
+            let result = az_smart_contract_hub.update_storage_deposit(5);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // when called by a fee manager
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            az_smart_contract_hub
+                .grant_role(HubRole::FeeManager, accounts.django)
+                .unwrap();
+            set_caller::<DefaultEnvironment>(accounts.django);
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.django));
+            // * it updates the storage deposit
+            az_smart_contract_hub.update_storage_deposit(5).unwrap();
+            assert_eq!(az_smart_contract_hub.storage_deposit, 5);
+            // * it is charged on create
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(az_smart_contract_hub.fee);
+            let result = az_smart_contract_hub.create(
+                accounts.alice,
+                0,
+                Some(MOCK_AZERO_ID.to_string()),
+                MOCK_ABI_URL.to_string(),
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+                None,
+            );
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Insufficient deposit".to_string()
+                ))
+            );
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            assert_eq!(az_smart_contract_hub.deposits.get(0), Some(5));
         }
 
         #[ink::test]
-        fn test_show() {
+        fn test_update_storage_deposit_per_byte() {
             let (accounts, mut az_smart_contract_hub) = init();
-            // = when smart_contract does not exist
-            // * it returns error
-            assert_eq!(
-                az_smart_contract_hub.show(0),
-                Err(AZSmartContractHubError::NotFound(
-                    "SmartContract".to_string()
-                ))
-            );
-            // = when smart_contract exists
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(
-                az_smart_contract_hub.fee,
+            // when called by non fee manager
+            set_caller::<DefaultEnvironment>(accounts.django);
+            // * it raises an error
+            let result = az_smart_contract_hub.update_storage_deposit_per_byte(2);
+            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
+            // when called by a fee manager
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            // * it updates the storage deposit per byte
+            az_smart_contract_hub
+                .update_storage_deposit_per_byte(2)
+                .unwrap();
+            assert_eq!(az_smart_contract_hub.storage_deposit_per_byte, 2);
+            // * it charges the deposit on create according to the size of the smart contract
+            // * it refunds any amount over the required deposit
+            set_caller::<DefaultEnvironment>(accounts.charlie);
+            azero_id_router::in_memory::set_address(MOCK_AZERO_ID, Some(accounts.charlie));
+            let balance_before: Balance =
+                ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.charlie)
+                    .unwrap();
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(
+                az_smart_contract_hub.fee + 10_000,
             );
             let smart_contract: SmartContract = az_smart_contract_hub
                 .create(
                     accounts.alice,
                     0,
-                    MOCK_AZERO_ID.to_string(),
+                    Some(MOCK_AZERO_ID.to_string()),
                     MOCK_ABI_URL.to_string(),
                     None,
                     None,
@@ -458,182 +4215,388 @@ mod az_smart_contract_hub {
                     None,
                 )
                 .unwrap();
-            // = * it returns the smart_contract
+            let deposit: Balance = 2 * scale::Encode::encoded_size(&smart_contract) as Balance;
+            assert_eq!(az_smart_contract_hub.deposit_of(0).unwrap(), deposit);
             assert_eq!(
-                az_smart_contract_hub.show(smart_contract.id),
-                Ok(smart_contract)
+                ink::env::test::get_account_balance::<DefaultEnvironment>(accounts.charlie)
+                    .unwrap(),
+                balance_before + 10_000 - deposit
             );
-        }
-
-        // === TEST HANDLES ===
-        #[ink::test]
-        fn test_create() {
-            let (accounts, mut az_smart_contract_hub) = init();
-            // when smart_contracts_count is u32::MAX
-            az_smart_contract_hub.smart_contracts_count = u32::MAX;
-            // * it raises an error
-            let result = az_smart_contract_hub.create(
-                accounts.alice,
-                0,
-                MOCK_AZERO_ID.to_string(),
-                MOCK_ABI_URL.to_string(),
-                Some(MOCK_CONTRACT_URL.to_string()),
-                Some(MOCK_WASM_URL.to_string()),
-                Some(MOCK_AUDIT_URL.to_string()),
-                Some(5),
-                Some(MOCK_PROJECT_NAME.to_string()),
-                Some(MOCK_PROJECT_WEBSITE.to_string()),
-                Some(MOCK_GITHUB.to_string()),
+            // * it charges the deposit when the smart contract grows
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(0);
+            let result = az_smart_contract_hub.update_tags(0, vec!["DeFi".to_string()]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Insufficient deposit".to_string()
+                ))
             );
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(10);
+            az_smart_contract_hub
+                .update_tags(0, vec!["DeFi".to_string()])
+                .unwrap();
+            assert_eq!(az_smart_contract_hub.deposit_of(0).unwrap(), deposit + 10);
+            // * it refunds the deposit when the smart contract shrinks
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(0);
+            az_smart_contract_hub.update_tags(0, vec![]).unwrap();
+            assert_eq!(az_smart_contract_hub.deposit_of(0).unwrap(), deposit);
+            // * it raises an error when the deposit would overflow
+            az_smart_contract_hub.deposits.insert(0, &Balance::MAX);
+            ink::env::test::set_value_transferred::<DefaultEnvironment>(1);
+            let result = az_smart_contract_hub.update_tags(0, vec![]);
             assert_eq!(
                 result,
                 Err(AZSmartContractHubError::UnprocessableEntity(
-                    "Smart contract limit reached".to_string(),
+                    "Deposit is too large".to_string()
                 ))
             );
-            // when smart_contracts_count is less than u32::MAX
-            // * tested below
         }
 
         #[ink::test]
-        fn test_update() {
+        fn test_update_tags() {
             let (accounts, mut az_smart_contract_hub) = init();
             // = when smart_contract doesn't exist
             // = * it raises an error
-            let mut result = az_smart_contract_hub.update(
-                0,
-                false,
-                MOCK_AZERO_ID.to_string(),
-                None,
-                None,
-                None,
-                None,
-                None,
-            );
+            let mut result = az_smart_contract_hub.update_tags(0, vec!["dex".to_string()]);
             assert_eq!(
                 result,
                 Err(AZSmartContractHubError::NotFound(
                     "SmartContract".to_string()
                 ))
             );
-
             // = when smart_contract exists
-            ink::env::test::set_value_transferred::<ink::env::DefaultEnvironment>(
-                az_smart_contract_hub.fee,
-            );
-            az_smart_contract_hub
-                .create(
-                    accounts.alice,
-                    0,
-                    MOCK_AZERO_ID.to_string(),
-                    MOCK_ABI_URL.to_string(),
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                    None,
-                )
-                .unwrap();
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
             // == when called by account that is not the original caller
             set_caller::<DefaultEnvironment>(accounts.charlie);
             // == * it raises an error
-            result = az_smart_contract_hub.update(
-                0,
-                false,
-                MOCK_AZERO_ID.to_string(),
-                None,
-                None,
-                None,
-                None,
-                None,
-            );
+            result = az_smart_contract_hub.update_tags(0, vec!["dex".to_string()]);
             assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
             // == when called by account that is the original caller
             set_caller::<DefaultEnvironment>(accounts.bob);
-            result = az_smart_contract_hub.update(
+            // === when a tag is blank
+            // === * it raises an error
+            result = az_smart_contract_hub.update_tags(0, vec![" ".to_string()]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Tag can't be blank".to_string()
+                ))
+            );
+            // === when a tag is too long
+            // === * it raises an error
+            result = az_smart_contract_hub.update_tags(0, vec!["a".repeat(TAG_LENGTH_LIMIT + 1)]);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                    "Tag can't be longer than {TAG_LENGTH_LIMIT} bytes"
+                )))
+            );
+            // === when there are too many tags
+            // === * it raises an error
+            result = az_smart_contract_hub
+                .update_tags(0, (0..=TAGS_LIMIT).map(|i| format!("tag{i}")).collect());
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(format!(
+                    "Tags limit is {TAGS_LIMIT}"
+                )))
+            );
+            // === when tags are valid
+            result = az_smart_contract_hub.update_tags(
                 0,
-                false,
-                MOCK_AZERO_ID_TWO.to_string(),
-                Some(412),
-                Some(MOCK_AUDIT_URL.to_string()),
-                Some(MOCK_PROJECT_NAME.to_string()),
-                Some(MOCK_PROJECT_WEBSITE.to_string()),
-                Some(MOCK_GITHUB.to_string()),
+                vec![" DEX ".to_string(), "dex".to_string(), "Router".to_string()],
             );
-            let result_unwrapped = result.unwrap();
-            // == * it updates the enabled status
-            assert_eq!(result_unwrapped.enabled, false);
-            // == * it updates the azero id
-            assert_eq!(result_unwrapped.azero_id, MOCK_AZERO_ID_TWO.to_string());
-            // == * it updates the group id
-            assert_eq!(result_unwrapped.group_id, Some(412));
-            // == * it updates the audit url
-            assert_eq!(result_unwrapped.audit_url, Some(MOCK_AUDIT_URL.to_string()));
-            // == * it updates the project name
+            // === * it stores the tags trimmed, lowercased and deduplicated
             assert_eq!(
-                result_unwrapped.project_name,
-                Some(MOCK_PROJECT_NAME.to_string())
+                result.unwrap().tags,
+                vec!["dex".to_string(), "router".to_string()]
             );
-            // == * it updates the project website
+            // === * it indexes the smart contract by tag
             assert_eq!(
-                result_unwrapped.project_website,
-                Some(MOCK_PROJECT_WEBSITE.to_string())
+                smart_contract_ids_by_tag(&az_smart_contract_hub, "dex"),
+                vec![0]
+            );
+            assert_eq!(
+                smart_contract_ids_by_tag(&az_smart_contract_hub, "router"),
+                vec![0]
+            );
+            // === when a tag is removed
+            az_smart_contract_hub
+                .update_tags(0, vec!["router".to_string(), "psp22".to_string()])
+                .unwrap();
+            // === * it removes the smart contract from that tag's index
+            assert!(smart_contract_ids_by_tag(&az_smart_contract_hub, "dex").is_empty());
+            assert!(az_smart_contract_hub
+                .smart_contracts_count_by_tag
+                .get("dex".to_string())
+                .is_none());
+            assert_eq!(
+                smart_contract_ids_by_tag(&az_smart_contract_hub, "router"),
+                vec![0]
+            );
+            assert_eq!(
+                smart_contract_ids_by_tag(&az_smart_contract_hub, "psp22"),
+                vec![0]
+            );
+            // === when a smart contract before the end of a tag's index is removed
+            for _ in 0..2 {
+                let smart_contract: SmartContract =
+                    create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+                az_smart_contract_hub
+                    .update_tags(smart_contract.id, vec!["router".to_string()])
+                    .unwrap();
+            }
+            az_smart_contract_hub.update_tags(0, vec![]).unwrap();
+            // === * it moves the last smart contract into its position
+            assert_eq!(
+                smart_contract_ids_by_tag(&az_smart_contract_hub, "router"),
+                vec![2, 1]
+            );
+            assert_eq!(
+                az_smart_contract_hub
+                    .smart_contract_positions_by_tag
+                    .get(("router".to_string(), 2)),
+                Some(0)
             );
-            // == * it updates the github
-            assert_eq!(result_unwrapped.github, Some(MOCK_GITHUB.to_string()));
         }
 
+        // Successful verification calls the smart contract, which can't be done in unit tests.
         #[ink::test]
-        fn test_update_fee() {
+        fn test_verify_interface() {
             let (accounts, mut az_smart_contract_hub) = init();
-            // when called by non admin
-            set_caller::<DefaultEnvironment>(accounts.django);
-            // * it raises an error
-            let result = az_smart_contract_hub.update_fee(5);
-            assert_eq!(result, Err(AZSmartContractHubError::Unauthorised));
-            // when called by the admin
-            set_caller::<DefaultEnvironment>(accounts.bob);
-            // * it updates the fee
-            az_smart_contract_hub.update_fee(5).unwrap();
-            assert_eq!(az_smart_contract_hub.fee, 5)
+            // = when smart_contract doesn't exist
+            // = * it raises an error
+            let mut result = az_smart_contract_hub.verify_interface(0, 0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "SmartContract".to_string()
+                ))
+            );
+            // = when smart_contract exists
+            create_smart_contract(&mut az_smart_contract_hub, accounts.alice);
+            az_smart_contract_hub
+                .interfaces_create("PSP22".to_string(), None)
+                .unwrap();
+            // == when interface hasn't been declared
+            // == * it raises an error
+            result = az_smart_contract_hub.verify_interface(0, 0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::NotFound(
+                    "InterfaceDeclaration".to_string()
+                ))
+            );
+            // == when interface has been declared
+            az_smart_contract_hub.update_interfaces(0, vec![0]).unwrap();
+            // === when interface doesn't have a selector
+            // === * it raises an error
+            result = az_smart_contract_hub.verify_interface(0, 0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Interface can't be verified".to_string()
+                ))
+            );
+            // === when smart contract is on a different chain
+            az_smart_contract_hub.chain = 1;
+            // === * it raises an error
+            result = az_smart_contract_hub.verify_interface(0, 0);
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contract is on a different chain".to_string()
+                ))
+            );
         }
     }
 
-    // The main purpose of the e2e tests are to test the interactions with az groups contract
-    #[cfg(all(test, feature = "e2e-tests"))]
-    mod e2e_tests {
-        use super::*;
-        use crate::az_smart_contract_hub::AZSmartContractHubRef;
-        use az_groups::AZGroupsRef;
-        use ink_e2e::build_message;
-        use ink_e2e::Keypair;
+    // The main purpose of the e2e tests are to test the interactions with az groups contract
+    #[cfg(all(test, feature = "e2e-tests"))]
+    mod e2e_tests {
+        use super::*;
+        use crate::az_smart_contract_hub::AZSmartContractHubRef;
+        use az_groups::AZGroupsRef;
+        use az_groups_types::{AZGroupsError, AZGroupsMembership, Role};
+        use ink_e2e::build_message;
+        use ink_e2e::Keypair;
+        use mock_azero_id_router::{MockAzeroIdRouterRef, DOMAIN_NOT_FOUND};
+        use mock_ownable::MockOwnableRef;
+
+        // === CONSTANTS ===
+        const MOCK_ABSENT_AZERO_ID: &str = "MOCK ABSENT AZERO ID";
+        const MOCK_INVALID_AZERO_ID: &str = "MOCK INVALID AZERO ID";
+        const MOCK_VALID_AZERO_ID: &str = "MOCK VALID AZERO ID";
+        const MOCK_ABI_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/tmuurccd5a7lcvin6ae9.json";
+        const MOCK_CONTRACT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/vsvsvavdvavav.json";
+        const MOCK_WASM_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/ffbrgnteyjytntehthw34hhhwhwhwnq343.json";
+        const MOCK_AUDIT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/mlkmkbdsbmdsb3rrg3m.json";
+        const MOCK_PROJECT_NAME: &str = "Smart Contract Hub";
+        const MOCK_PROJECT_WEBSITE: &str = "https://someprojectwebsite.org/projects/project-name";
+        const MOCK_GITHUB: &str = "https://github.com/smart-contract-hub/project-name";
+
+        // === TYPES ===
+        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+
+        // === HELPERS ===
+        fn account_id(k: Keypair) -> AccountId {
+            AccountId::try_from(k.public_key().to_account_id().as_ref())
+                .expect("account keyring has a valid account id")
+        }
+
+        // === HANDLES ===
+        #[ink_e2e::test]
+        async fn test_claim_canonical(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Instantiate AZ Groups
+            let az_groups_contstructor = AZGroupsRef::new();
+            let az_groups_account_id = client
+                .instantiate(
+                    "az_groups",
+                    &ink_e2e::alice(),
+                    az_groups_contstructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("AZ Groups instantiate failed")
+                .account_id;
+
+            // Instantiate mock AZERO.ID router
+            let mock_azero_id_router_constructor = MockAzeroIdRouterRef::new();
+            let mock_azero_id_router_account_id = client
+                .instantiate(
+                    "mock_azero_id_router",
+                    &ink_e2e::alice(),
+                    mock_azero_id_router_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("Mock AZERO.ID router instantiate failed")
+                .account_id;
+            for (domain, address) in [
+                (MOCK_VALID_AZERO_ID, account_id(ink_e2e::alice())),
+                (MOCK_INVALID_AZERO_ID, account_id(ink_e2e::bob())),
+            ] {
+                let set_address_message =
+                    build_message::<MockAzeroIdRouterRef>(mock_azero_id_router_account_id.clone())
+                        .call(|mock_azero_id_router| {
+                            mock_azero_id_router.set_address(domain.to_string(), Some(address))
+                        });
+                client
+                    .call(&ink_e2e::alice(), set_address_message, 0, None)
+                    .await
+                    .unwrap();
+            }
+
+            // Instantiate mock ownable smart contract, owned by alice
+            let mock_ownable_constructor = MockOwnableRef::new();
+            let mock_ownable_account_id = client
+                .instantiate(
+                    "mock_ownable",
+                    &ink_e2e::alice(),
+                    mock_ownable_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("Mock ownable instantiate failed")
+                .account_id;
 
-        // === CONSTANTS ===
-        const MOCK_ABSENT_AZERO_ID: &str = "MOCK ABSENT AZERO ID";
-        const MOCK_ABI_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/tmuurccd5a7lcvin6ae9.json";
-        const MOCK_CONTRACT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/vsvsvavdvavav.json";
-        const MOCK_WASM_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/ffbrgnteyjytntehthw34hhhwhwhwnq343.json";
-        const MOCK_AUDIT_URL: &str = "https://res.mockcdn.com/xasdf123/raw/upload/v1690808298/smart_contract_hub/mlkmkbdsbmdsb3rrg3m.json";
-        const MOCK_PROJECT_NAME: &str = "Smart Contract Hub";
-        const MOCK_PROJECT_WEBSITE: &str = "https://someprojectwebsite.org/projects/project-name";
-        const MOCK_GITHUB: &str = "https://github.com/smart-contract-hub/project-name";
+            // Instantiate AZSmartContractHub
+            let az_smart_contract_hub_constructor = AZSmartContractHubRef::new(
+                mock_azero_id_router_account_id,
+                az_groups_account_id,
+                0,
+            );
+            let az_smart_contract_hub_id = client
+                .instantiate(
+                    "az_smart_contract_hub",
+                    &ink_e2e::eve(),
+                    az_smart_contract_hub_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("AZ Smart Contract Hub instantiate failed")
+                .account_id;
+            let finalise_import_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.finalise_import());
+            client
+                .call(&ink_e2e::eve(), finalise_import_message, 0, None)
+                .await
+                .expect("Finalise import failed");
 
-        // === TYPES ===
-        type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
+            // Create a smart contract record for the mock ownable by alice and by bob
+            for (azero_id, keypair) in [
+                (MOCK_VALID_AZERO_ID, ink_e2e::alice()),
+                (MOCK_INVALID_AZERO_ID, ink_e2e::bob()),
+            ] {
+                let create_message = build_message::<AZSmartContractHubRef>(
+                    az_smart_contract_hub_id.clone(),
+                )
+                .call(|az_smart_contract_hub| {
+                    az_smart_contract_hub.create(
+                        mock_ownable_account_id,
+                        0,
+                        Some(azero_id.to_string()),
+                        MOCK_ABI_URL.to_string(),
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                        None,
+                    )
+                });
+                client
+                    .call(&keypair, create_message, 1_000, None)
+                    .await
+                    .expect("Create failed");
+            }
 
-        // === HELPERS ===
-        fn account_id(k: Keypair) -> AccountId {
-            AccountId::try_from(k.public_key().to_account_id().as_ref())
-                .expect("account keyring has a valid account id")
-        }
+            // = when the caller is not the owner of the smart contract
+            let mut claim_canonical_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.claim_canonical(1));
+            // = * it raises an error
+            let result = client
+                .call_dry_run(&ink_e2e::bob(), &claim_canonical_message, 0, None)
+                .await
+                .return_value();
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Caller is not the owner of the smart contract".to_string()
+                ))
+            );
+            // = when the caller is the owner of the smart contract
+            claim_canonical_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.claim_canonical(0));
+            client
+                .call(&ink_e2e::alice(), claim_canonical_message, 0, None)
+                .await
+                .expect("Claim canonical failed");
+            // = * it makes the smart contract record canonical
+            let canonical_for_message = build_message::<AZSmartContractHubRef>(
+                az_smart_contract_hub_id.clone(),
+            )
+            .call(|az_smart_contract_hub| {
+                az_smart_contract_hub.canonical_for(0, mock_ownable_account_id)
+            });
+            let smart_contract: SmartContract = client
+                .call_dry_run(&ink_e2e::alice(), &canonical_for_message, 0, None)
+                .await
+                .return_value()
+                .unwrap();
+            assert_eq!(smart_contract.id, 0);
 
-        fn mock_azero_id_router_address() -> AccountId {
-            AccountId::try_from(*b"xxxxxxxxxxxxxxxxxxxxxxxxxxxxxxxx").unwrap()
+            Ok(())
         }
 
-        // === HANDLES ===
         #[ink_e2e::test]
         async fn test_create(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {
             // Instantiate AZ Groups
@@ -650,9 +4613,40 @@ mod az_smart_contract_hub {
                 .expect("AZ Groups instantiate failed")
                 .account_id;
 
+            // Instantiate mock AZERO.ID router
+            let mock_azero_id_router_constructor = MockAzeroIdRouterRef::new();
+            let mock_azero_id_router_account_id = client
+                .instantiate(
+                    "mock_azero_id_router",
+                    &ink_e2e::alice(),
+                    mock_azero_id_router_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("Mock AZERO.ID router instantiate failed")
+                .account_id;
+            for (domain, address) in [
+                (MOCK_VALID_AZERO_ID, account_id(ink_e2e::alice())),
+                (MOCK_INVALID_AZERO_ID, account_id(ink_e2e::bob())),
+            ] {
+                let set_address_message =
+                    build_message::<MockAzeroIdRouterRef>(mock_azero_id_router_account_id.clone())
+                        .call(|mock_azero_id_router| {
+                            mock_azero_id_router.set_address(domain.to_string(), Some(address))
+                        });
+                client
+                    .call(&ink_e2e::alice(), set_address_message, 0, None)
+                    .await
+                    .unwrap();
+            }
+
             // Instantiate AZSmartContractHub
-            let az_smart_contract_hub_constructor =
-                AZSmartContractHubRef::new(mock_azero_id_router_address(), az_groups_account_id);
+            let az_smart_contract_hub_constructor = AZSmartContractHubRef::new(
+                mock_azero_id_router_account_id,
+                az_groups_account_id,
+                0,
+            );
             let az_smart_contract_hub_id = client
                 .instantiate(
                     "az_smart_contract_hub",
@@ -664,6 +4658,13 @@ mod az_smart_contract_hub {
                 .await
                 .expect("AZ Smart Contract Hub instantiate failed")
                 .account_id;
+            let finalise_import_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.finalise_import());
+            client
+                .call(&ink_e2e::eve(), finalise_import_message, 0, None)
+                .await
+                .expect("Finalise import failed");
 
             // when count is u32::MAX
             // * tested above
@@ -676,7 +4677,7 @@ mod az_smart_contract_hub {
                 az_smart_contract_hub.create(
                     account_id(ink_e2e::eve()),
                     0,
-                    MOCK_ABSENT_AZERO_ID.to_string(),
+                    Some(MOCK_ABSENT_AZERO_ID.to_string()),
                     MOCK_ABI_URL.to_string(),
                     Some(MOCK_CONTRACT_URL.to_string()),
                     Some(MOCK_WASM_URL.to_string()),
@@ -694,7 +4695,9 @@ mod az_smart_contract_hub {
             // = * it raises an error
             assert_eq!(
                 result,
-                Err(AZSmartContractHubError::NotFound("Domain".to_string()))
+                Err(AZSmartContractHubError::AzeroIdRouterError(
+                    DOMAIN_NOT_FOUND
+                ))
             );
             // = when azero id exists
             // == when caller doesn't own azero id
@@ -706,7 +4709,7 @@ mod az_smart_contract_hub {
                 az_smart_contract_hub.create(
                     account_id(ink_e2e::eve()),
                     0,
-                    MOCK_INVALID_AZERO_ID.to_string(),
+                    Some(MOCK_INVALID_AZERO_ID.to_string()),
                     MOCK_ABI_URL.to_string(),
                     Some(MOCK_CONTRACT_URL.to_string()),
                     Some(MOCK_WASM_URL.to_string()),
@@ -738,7 +4741,7 @@ mod az_smart_contract_hub {
                 az_smart_contract_hub.create(
                     account_id(ink_e2e::eve()),
                     0,
-                    MOCK_VALID_AZERO_ID.to_string(),
+                    Some(MOCK_VALID_AZERO_ID.to_string()),
                     MOCK_ABI_URL.to_string(),
                     Some(MOCK_CONTRACT_URL.to_string()),
                     Some(MOCK_WASM_URL.to_string()),
@@ -761,8 +4764,9 @@ mod az_smart_contract_hub {
             );
             // === when group exists
             let mut create_group_message =
-                build_message::<AZGroupsRef>(az_groups_account_id.clone())
-                    .call(|az_groups| az_groups.groups_create("Eve's team".to_string()));
+                build_message::<AZGroupsRef>(az_groups_account_id.clone()).call(|az_groups| {
+                    az_groups.groups_create("Eve's team".to_string(), None, None, None, None)
+                });
             let mut groups_result = client
                 .call(&ink_e2e::eve(), create_group_message, 0, None)
                 .await
@@ -783,8 +4787,10 @@ mod az_smart_contract_hub {
                 ))
             );
             // ==== when user is a member of the group
-            create_group_message = build_message::<AZGroupsRef>(az_groups_account_id.clone())
-                .call(|az_groups| az_groups.groups_create("Alice's team".to_string()));
+            create_group_message =
+                build_message::<AZGroupsRef>(az_groups_account_id.clone()).call(|az_groups| {
+                    az_groups.groups_create("Alice's team".to_string(), None, None, None, None)
+                });
             groups_result = client
                 .call(&ink_e2e::alice(), create_group_message, 0, None)
                 .await
@@ -802,7 +4808,7 @@ mod az_smart_contract_hub {
                 az_smart_contract_hub.create(
                     account_id(ink_e2e::eve()),
                     0,
-                    MOCK_VALID_AZERO_ID.to_string(),
+                    Some(MOCK_VALID_AZERO_ID.to_string()),
                     " ".to_string(),
                     Some(MOCK_CONTRACT_URL.to_string()),
                     Some(MOCK_WASM_URL.to_string()),
@@ -834,7 +4840,7 @@ mod az_smart_contract_hub {
                 az_smart_contract_hub.create(
                     account_id(ink_e2e::eve()),
                     0,
-                    MOCK_VALID_AZERO_ID.to_string(),
+                    Some(MOCK_VALID_AZERO_ID.to_string()),
                     mock_abi_url_with_whitespaces.clone(),
                     Some(MOCK_CONTRACT_URL.to_string()),
                     Some(MOCK_WASM_URL.to_string()),
@@ -928,5 +4934,303 @@ mod az_smart_contract_hub {
 
             Ok(())
         }
+
+        // Breaks if the selector the hub calls diverges from the one AZ Groups exposes.
+        #[ink_e2e::test]
+        async fn test_validate_membership(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Instantiate AZ Groups
+            let az_groups_contstructor = AZGroupsRef::new();
+            let az_groups_account_id = client
+                .instantiate(
+                    "az_groups",
+                    &ink_e2e::alice(),
+                    az_groups_contstructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("AZ Groups instantiate failed")
+                .account_id;
+            let create_group_message = build_message::<AZGroupsRef>(az_groups_account_id.clone())
+                .call(|az_groups| {
+                    az_groups.groups_create("Alice's team".to_string(), None, None, None, None)
+                });
+            client
+                .call(&ink_e2e::alice(), create_group_message, 0, None)
+                .await
+                .unwrap();
+
+            // Instantiate mock AZERO.ID router
+            let mock_azero_id_router_constructor = MockAzeroIdRouterRef::new();
+            let mock_azero_id_router_account_id = client
+                .instantiate(
+                    "mock_azero_id_router",
+                    &ink_e2e::alice(),
+                    mock_azero_id_router_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("Mock AZERO.ID router instantiate failed")
+                .account_id;
+            let set_address_message =
+                build_message::<MockAzeroIdRouterRef>(mock_azero_id_router_account_id.clone())
+                    .call(|mock_azero_id_router| {
+                        mock_azero_id_router.set_address(
+                            MOCK_VALID_AZERO_ID.to_string(),
+                            Some(account_id(ink_e2e::alice())),
+                        )
+                    });
+            client
+                .call(&ink_e2e::alice(), set_address_message, 0, None)
+                .await
+                .unwrap();
+
+            // Instantiate AZSmartContractHub
+            let az_smart_contract_hub_constructor = AZSmartContractHubRef::new(
+                mock_azero_id_router_account_id,
+                az_groups_account_id,
+                0,
+            );
+            let az_smart_contract_hub_id = client
+                .instantiate(
+                    "az_smart_contract_hub",
+                    &ink_e2e::eve(),
+                    az_smart_contract_hub_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("AZ Smart Contract Hub instantiate failed")
+                .account_id;
+            let finalise_import_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.finalise_import());
+            client
+                .call(&ink_e2e::eve(), finalise_import_message, 0, None)
+                .await
+                .expect("Finalise import failed");
+
+            // = when called on AZ Groups through the typed interface
+            let validate_membership_message =
+                build_message::<AZGroupsRef>(az_groups_account_id.clone()).call(|az_groups| {
+                    az_groups.validate_membership(0, account_id(ink_e2e::alice()))
+                });
+            // = * it returns the role
+            let role_result = client
+                .call_dry_run(&ink_e2e::alice(), &validate_membership_message, 0, None)
+                .await
+                .return_value();
+            assert_eq!(role_result, Ok(Role::SuperAdmin));
+            // = when called by the hub
+            let create_message = build_message::<AZSmartContractHubRef>(
+                az_smart_contract_hub_id.clone(),
+            )
+            .call(|az_smart_contract_hub| {
+                az_smart_contract_hub.create(
+                    account_id(ink_e2e::eve()),
+                    0,
+                    Some(MOCK_VALID_AZERO_ID.to_string()),
+                    MOCK_ABI_URL.to_string(),
+                    None,
+                    None,
+                    None,
+                    Some(0),
+                    None,
+                    None,
+                    None,
+                )
+            });
+            // = * it validates the membership
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &create_message, 1_000, None)
+                .await
+                .return_value();
+            assert_eq!(result.unwrap().group_id, Some(0));
+            client
+                .call(&ink_e2e::alice(), create_message, 1_000, None)
+                .await
+                .expect("Create failed");
+            let show_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.show(0));
+            let smart_contract: SmartContract = client
+                .call_dry_run(&ink_e2e::alice(), &show_message, 0, None)
+                .await
+                .return_value()
+                .unwrap();
+            assert_eq!(smart_contract.group_id, Some(0));
+
+            Ok(())
+        }
+
+        // AZ Groups stands in for a smart contract that implements an interface,
+        // with its config message as the interface's selector.
+        #[ink_e2e::test]
+        async fn test_verify_interface(mut client: ::ink_e2e::Client<C, E>) -> E2EResult<()> {
+            // Instantiate AZ Groups
+            let az_groups_contstructor = AZGroupsRef::new();
+            let az_groups_account_id = client
+                .instantiate(
+                    "az_groups",
+                    &ink_e2e::alice(),
+                    az_groups_contstructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("AZ Groups instantiate failed")
+                .account_id;
+
+            // Instantiate mock AZERO.ID router
+            let mock_azero_id_router_constructor = MockAzeroIdRouterRef::new();
+            let mock_azero_id_router_account_id = client
+                .instantiate(
+                    "mock_azero_id_router",
+                    &ink_e2e::alice(),
+                    mock_azero_id_router_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("Mock AZERO.ID router instantiate failed")
+                .account_id;
+            let set_address_message =
+                build_message::<MockAzeroIdRouterRef>(mock_azero_id_router_account_id.clone())
+                    .call(|mock_azero_id_router| {
+                        mock_azero_id_router.set_address(
+                            MOCK_VALID_AZERO_ID.to_string(),
+                            Some(account_id(ink_e2e::alice())),
+                        )
+                    });
+            client
+                .call(&ink_e2e::alice(), set_address_message, 0, None)
+                .await
+                .unwrap();
+
+            // Instantiate AZSmartContractHub
+            let az_smart_contract_hub_constructor = AZSmartContractHubRef::new(
+                mock_azero_id_router_account_id,
+                az_groups_account_id,
+                0,
+            );
+            let az_smart_contract_hub_id = client
+                .instantiate(
+                    "az_smart_contract_hub",
+                    &ink_e2e::eve(),
+                    az_smart_contract_hub_constructor,
+                    0,
+                    None,
+                )
+                .await
+                .expect("AZ Smart Contract Hub instantiate failed")
+                .account_id;
+            let finalise_import_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.finalise_import());
+            client
+                .call(&ink_e2e::eve(), finalise_import_message, 0, None)
+                .await
+                .expect("Finalise import failed");
+
+            // Create interfaces with a selector AZ Groups implements and one it doesn't
+            for (name, selector) in [
+                ("AZ Groups", ink::selector_bytes!("config")),
+                ("Unimplemented", [0, 0, 0, 0]),
+            ] {
+                let interfaces_create_message = build_message::<AZSmartContractHubRef>(
+                    az_smart_contract_hub_id.clone(),
+                )
+                .call(|az_smart_contract_hub| {
+                    az_smart_contract_hub.interfaces_create(name.to_string(), Some(selector))
+                });
+                client
+                    .call(&ink_e2e::eve(), interfaces_create_message, 0, None)
+                    .await
+                    .unwrap();
+            }
+
+            // Create a smart contract record for AZ Groups declaring both interfaces
+            let create_message = build_message::<AZSmartContractHubRef>(
+                az_smart_contract_hub_id.clone(),
+            )
+            .call(|az_smart_contract_hub| {
+                az_smart_contract_hub.create(
+                    az_groups_account_id,
+                    0,
+                    Some(MOCK_VALID_AZERO_ID.to_string()),
+                    MOCK_ABI_URL.to_string(),
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                    None,
+                )
+            });
+            client
+                .call(&ink_e2e::alice(), create_message, 1_000, None)
+                .await
+                .expect("Create failed");
+            let update_interfaces_message = build_message::<AZSmartContractHubRef>(
+                az_smart_contract_hub_id.clone(),
+            )
+            .call(|az_smart_contract_hub| az_smart_contract_hub.update_interfaces(0, vec![0, 1]));
+            client
+                .call(&ink_e2e::alice(), update_interfaces_message, 0, None)
+                .await
+                .expect("Update interfaces failed");
+
+            // = when the smart contract does not implement the selector
+            let mut verify_interface_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.verify_interface(0, 1));
+            // = * it raises an error
+            let result = client
+                .call_dry_run(&ink_e2e::bob(), &verify_interface_message, 0, None)
+                .await
+                .return_value();
+            assert_eq!(
+                result,
+                Err(AZSmartContractHubError::UnprocessableEntity(
+                    "Smart contract does not implement interface".to_string()
+                ))
+            );
+            // = when the smart contract implements the selector
+            verify_interface_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.verify_interface(0, 0));
+            client
+                .call(&ink_e2e::bob(), verify_interface_message, 0, None)
+                .await
+                .expect("Verify interface failed");
+            // = * it marks the declaration as verified
+            let show_message =
+                build_message::<AZSmartContractHubRef>(az_smart_contract_hub_id.clone())
+                    .call(|az_smart_contract_hub| az_smart_contract_hub.show(0));
+            let smart_contract: SmartContract = client
+                .call_dry_run(&ink_e2e::alice(), &show_message, 0, None)
+                .await
+                .return_value()
+                .unwrap();
+            assert_eq!(
+                smart_contract.interfaces,
+                vec![
+                    InterfaceDeclaration {
+                        interface_id: 0,
+                        verified: true,
+                        selector_version: 0
+                    },
+                    InterfaceDeclaration {
+                        interface_id: 1,
+                        verified: false,
+                        selector_version: 0
+                    }
+                ]
+            );
+
+            Ok(())
+        }
     }
 }
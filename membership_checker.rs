@@ -0,0 +1,177 @@
+use az_groups_types::{AZGroupsError, Permission, Role};
+use ink::primitives::AccountId;
+
+// Validates that an account is a member of an enabled AZ Groups group,
+// and checks the permissions it has in the group.
+pub trait MembershipChecker {
+    fn validate_membership(&self, group_id: u32, account: AccountId)
+        -> Result<Role, AZGroupsError>;
+
+    fn has_permission(
+        &self,
+        group_id: u32,
+        account: AccountId,
+        permission: Permission,
+    ) -> Result<bool, AZGroupsError>;
+}
+
+// Calls the AZ Groups smart contract through its typed interface.
+#[cfg(not(test))]
+pub struct AZGroupsMembershipChecker {
+    pub az_groups_address: AccountId,
+}
+
+#[cfg(not(test))]
+impl MembershipChecker for AZGroupsMembershipChecker {
+    fn validate_membership(
+        &self,
+        group_id: u32,
+        account: AccountId,
+    ) -> Result<Role, AZGroupsError> {
+        use az_groups_types::AZGroupsMembership;
+        use ink::{codegen::TraitCallBuilder, contract_ref, env::DefaultEnvironment};
+
+        let az_groups: contract_ref!(AZGroupsMembership, DefaultEnvironment) =
+            self.az_groups_address.into();
+        az_groups
+            .call()
+            .validate_membership(group_id, account)
+            .try_invoke()??
+    }
+
+    fn has_permission(
+        &self,
+        group_id: u32,
+        account: AccountId,
+        permission: Permission,
+    ) -> Result<bool, AZGroupsError> {
+        use az_groups_types::AZGroupsMembership;
+        use ink::{codegen::TraitCallBuilder, contract_ref, env::DefaultEnvironment};
+
+        let az_groups: contract_ref!(AZGroupsMembership, DefaultEnvironment) =
+            self.az_groups_address.into();
+        Ok(az_groups
+            .call()
+            .has_permission(group_id, account, permission)
+            .try_invoke()??)
+    }
+}
+
+// In-memory stand-in for AZ Groups, used by unit tests like `azero_id_router::in_memory`.
+#[cfg(test)]
+pub mod in_memory {
+    use super::*;
+    use ink::prelude::string::ToString;
+    use std::{cell::RefCell, collections::BTreeMap};
+
+    thread_local! {
+        static GROUPS: RefCell<BTreeMap<u32, bool>> = const { RefCell::new(BTreeMap::new()) };
+        static GROUP_USERS: RefCell<BTreeMap<(u32, AccountId), Role>> = const { RefCell::new(BTreeMap::new()) };
+        static PERMISSIONS: RefCell<BTreeMap<(u32, AccountId), Vec<Permission>>> = const { RefCell::new(BTreeMap::new()) };
+        static ROLE_PERMISSIONS: RefCell<BTreeMap<(u32, u8), Vec<Permission>>> = const { RefCell::new(BTreeMap::new()) };
+    }
+
+    pub struct InMemoryMembershipChecker;
+
+    impl MembershipChecker for InMemoryMembershipChecker {
+        fn validate_membership(
+            &self,
+            group_id: u32,
+            account: AccountId,
+        ) -> Result<Role, AZGroupsError> {
+            let enabled: bool = GROUPS
+                .with(|groups| groups.borrow().get(&group_id).copied())
+                .ok_or(AZGroupsError::NotFound("Group".to_string()))?;
+            if !enabled {
+                return Err(AZGroupsError::GroupDisabled);
+            }
+            let role: Role = GROUP_USERS
+                .with(|group_users| group_users.borrow().get(&(group_id, account)).cloned())
+                .ok_or(AZGroupsError::NotFound("GroupUser".to_string()))?;
+            if matches!(role, Role::Banned | Role::Applicant) {
+                return Err(AZGroupsError::NotAMember);
+            }
+
+            Ok(role)
+        }
+
+        // Mirrors AZ Groups: members and above hold their role's permissions,
+        // which default to AZ Groups' defaults, along with their own grants.
+        fn has_permission(
+            &self,
+            group_id: u32,
+            account: AccountId,
+            permission: Permission,
+        ) -> Result<bool, AZGroupsError> {
+            let Some(role) = GROUP_USERS
+                .with(|group_users| group_users.borrow().get(&(group_id, account)).cloned())
+            else {
+                return Ok(false);
+            };
+            if role.to_int() < 2 {
+                return Ok(false);
+            }
+
+            Ok(role_permissions(group_id, role).contains(&permission)
+                || PERMISSIONS.with(|permissions| {
+                    permissions
+                        .borrow()
+                        .get(&(group_id, account))
+                        .is_some_and(|permissions| permissions.contains(&permission))
+                }))
+        }
+    }
+
+    fn role_permissions(group_id: u32, role: Role) -> Vec<Permission> {
+        match role {
+            Role::SuperAdmin => [
+                Permission::ManageMembers,
+                Permission::ManageInvitations,
+                Permission::EditGroup,
+                Permission::ManageHubRecords,
+            ]
+            .to_vec(),
+            _ => ROLE_PERMISSIONS
+                .with(|role_permissions| {
+                    role_permissions
+                        .borrow()
+                        .get(&(group_id, role.to_int()))
+                        .cloned()
+                })
+                .unwrap_or_else(|| match role {
+                    Role::Admin => [
+                        Permission::ManageMembers,
+                        Permission::ManageInvitations,
+                        Permission::ManageHubRecords,
+                    ]
+                    .to_vec(),
+                    Role::Member => [Permission::ManageHubRecords].to_vec(),
+                    _ => Vec::new(),
+                }),
+        }
+    }
+
+    pub fn set_group(group_id: u32, enabled: bool) {
+        GROUPS.with(|groups| groups.borrow_mut().insert(group_id, enabled));
+    }
+
+    pub fn set_group_user(group_id: u32, account: AccountId, role: Role) {
+        GROUP_USERS.with(|group_users| group_users.borrow_mut().insert((group_id, account), role));
+    }
+
+    pub fn set_role_permissions(group_id: u32, role: Role, permissions: Vec<Permission>) {
+        ROLE_PERMISSIONS.with(|role_permissions| {
+            role_permissions
+                .borrow_mut()
+                .insert((group_id, role.to_int()), permissions)
+        });
+    }
+
+    pub fn set_permissions(group_id: u32, account: AccountId, permissions: Vec<Permission>) {
+        PERMISSIONS.with(|permissions_by_user| {
+            permissions_by_user
+                .borrow_mut()
+                .insert((group_id, account), permissions)
+        });
+    }
+}
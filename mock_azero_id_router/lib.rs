@@ -0,0 +1,118 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_azero_id_router::{MockAzeroIdRouter, MockAzeroIdRouterRef, DOMAIN_NOT_FOUND};
+
+// A stand-in for the AZERO.ID router, used by the e2e tests.
+// Domains and primary domains are set directly instead of being registered.
+#[ink::contract]
+mod mock_azero_id_router {
+    use identity_resolver::IdentityResolver;
+    use ink::{
+        prelude::{string::String, vec::Vec},
+        storage::Mapping,
+    };
+
+    // === CONSTANTS ===
+    pub const DOMAIN_NOT_FOUND: u8 = 0;
+
+    // === CONTRACT ===
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct MockAzeroIdRouter {
+        addresses: Mapping<String, AccountId>,
+        primary_domains: Mapping<AccountId, String>,
+    }
+    impl MockAzeroIdRouter {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        // === HANDLES ===
+        #[ink(message)]
+        pub fn set_address(&mut self, domain: String, address: Option<AccountId>) {
+            if let Some(address_unwrapped) = address {
+                self.addresses.insert(domain, &address_unwrapped);
+            } else {
+                self.addresses.remove(domain);
+            }
+        }
+
+        #[ink(message)]
+        pub fn set_primary_domain(&mut self, account: AccountId, domain: Option<String>) {
+            if let Some(domain_unwrapped) = domain {
+                self.primary_domains.insert(account, &domain_unwrapped);
+            } else {
+                self.primary_domains.remove(account);
+            }
+        }
+    }
+
+    impl IdentityResolver for MockAzeroIdRouter {
+        #[ink(message)]
+        fn get_address(&self, domain: String) -> Result<AccountId, u8> {
+            self.addresses.get(domain).ok_or(DOMAIN_NOT_FOUND)
+        }
+
+        #[ink(message)]
+        fn get_primary_domains(
+            &self,
+            account: AccountId,
+            _tld: Option<String>,
+        ) -> Vec<(AccountId, String)> {
+            self.primary_domains
+                .get(account)
+                .map(|domain| (self.env().account_id(), domain))
+                .into_iter()
+                .collect()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{test::default_accounts, DefaultEnvironment};
+
+        #[ink::test]
+        fn test_get_address() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let mut mock_azero_id_router = MockAzeroIdRouter::new();
+            // when domain is not set
+            // * it returns the not found error code
+            assert_eq!(
+                mock_azero_id_router.get_address("alice.azero".to_string()),
+                Err(DOMAIN_NOT_FOUND)
+            );
+            // when domain is set
+            mock_azero_id_router.set_address("alice.azero".to_string(), Some(accounts.alice));
+            // * it returns the address
+            assert_eq!(
+                mock_azero_id_router.get_address("alice.azero".to_string()),
+                Ok(accounts.alice)
+            );
+        }
+
+        #[ink::test]
+        fn test_get_primary_domains() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            let mut mock_azero_id_router = MockAzeroIdRouter::new();
+            // when primary domain is not set
+            // * it returns an empty vector
+            assert_eq!(
+                mock_azero_id_router.get_primary_domains(accounts.alice, None),
+                vec![]
+            );
+            // when primary domain is set
+            mock_azero_id_router
+                .set_primary_domain(accounts.alice, Some("alice.azero".to_string()));
+            // * it returns the domain
+            assert_eq!(
+                mock_azero_id_router.get_primary_domains(accounts.alice, None),
+                vec![(
+                    ink::env::account_id::<DefaultEnvironment>(),
+                    "alice.azero".to_string()
+                )]
+            );
+        }
+    }
+}
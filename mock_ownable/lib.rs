@@ -0,0 +1,60 @@
+#![cfg_attr(not(feature = "std"), no_std, no_main)]
+
+pub use self::mock_ownable::{MockOwnable, MockOwnableRef};
+
+// The selector matches OpenBrush's `Ownable::owner`, which the hub calls to verify owners.
+#[ink::trait_definition]
+pub trait Ownable {
+    #[ink(message, selector = 0x4fa43c8c)]
+    fn owner(&self) -> Option<ink::primitives::AccountId>;
+}
+
+// A smart contract owned by its deployer, used by the e2e tests to claim canonical records.
+#[ink::contract]
+mod mock_ownable {
+    use crate::Ownable;
+
+    // === CONTRACT ===
+    #[ink(storage)]
+    pub struct MockOwnable {
+        owner: AccountId,
+    }
+    impl Default for MockOwnable {
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+    impl MockOwnable {
+        #[ink(constructor)]
+        pub fn new() -> Self {
+            Self {
+                owner: Self::env().caller(),
+            }
+        }
+    }
+
+    impl Ownable for MockOwnable {
+        #[ink(message)]
+        fn owner(&self) -> Option<AccountId> {
+            Some(self.owner)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::{
+            test::{default_accounts, set_caller},
+            DefaultEnvironment,
+        };
+
+        #[ink::test]
+        fn test_owner() {
+            let accounts = default_accounts::<DefaultEnvironment>();
+            set_caller::<DefaultEnvironment>(accounts.bob);
+            let mock_ownable = MockOwnable::new();
+            // * it returns the deployer
+            assert_eq!(mock_ownable.owner(), Some(accounts.bob));
+        }
+    }
+}